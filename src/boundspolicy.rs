@@ -0,0 +1,12 @@
+/// How out of range coordinates passed to [`set_draw_area`] are handled
+///
+/// [`set_draw_area`]: ./struct.Ssd1331.html#method.set_draw_area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BoundsPolicy {
+    /// Clamp coordinates past the edge of the display to the edge
+    Clamp,
+    /// Return an [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error
+    #[default]
+    Error,
+}
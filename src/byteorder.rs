@@ -0,0 +1,15 @@
+/// Order of the two bytes of each RGB565 pixel in the framebuffer
+///
+/// The default is big endian, or little endian if the `le-buffer` feature is enabled. The
+/// controller only accepts big endian pixel data, so a little endian framebuffer is byte swapped
+/// as it is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ByteOrder {
+    /// High byte first
+    #[cfg_attr(not(feature = "le-buffer"), default)]
+    BigEndian,
+    /// Low byte first, matching image data used by `ImageRawLE`
+    #[cfg_attr(feature = "le-buffer", default)]
+    LittleEndian,
+}
@@ -1,19 +1,23 @@
 // Shamefully taken from https://github.com/EdgewaterDevelopment/rust-ssd1331
 
 use crate::error::Error;
+use core::convert::TryFrom;
 use embedded_hal::digital::v2::OutputPin;
 
+/// Length of the longest encoded command, the gray scale table opcode and its 32 entries
+const MAX_COMMAND_LEN: usize = 33;
+
 /// SSD1331 Commands
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum Command {
     /// Set (r, g, b) contrast. Higher number is higher contrast.
     Contrast(u8, u8, u8),
-    /// Turn entire display on. If set, all pixels will
-    /// be set to on, if not, the value in memory will be used.
-    AllOn(bool),
-    /// Invert display.
-    Invert(bool),
+    /// Set master current attenuation from 0-15. All channels' segment current is scaled by
+    /// (value + 1) / 16.
+    MasterCurrent(u8),
+    /// Set display mode: normal, entire display on or off, or inverted
+    DisplayMode(DisplayMode),
     /// Turn display on or off.
     DisplayOn(bool),
     /// Setup column start and end address
@@ -28,28 +32,132 @@ pub enum Command {
     RemapAndColorDepth(bool, bool, ColorMode, AddressIncrementMode),
     /// Set multipex ratio from 15-63 (MUX-1)
     Multiplex(u8),
-    /// Scan from COM[n-1] to COM0 (where N is mux ratio)
-    ReverseComDir(bool),
     /// Set vertical shift
     DisplayOffset(u8),
-    /// Setup com hardware configuration
-    /// First value indicates sequential (false) or alternative (true)
-    /// pin configuration. Second value disables (false) or enables (true)
-    /// left/right remap.
-    ComPinConfig(bool, bool),
     /// Set up display clock.
     /// First value is oscillator frequency, increasing with higher value
     /// Second value is divide ratio - 1
     DisplayClockDiv(u8, u8),
     /// Set up phase 1 and 2 of precharge period. each value is from 0-63
     PreChargePeriod(u8, u8),
+    /// Set precharge voltage from 0 (0.1 x Vcc) to 31 (0.5 x Vcc)
+    PreChargeVoltage(u8),
     /// Set Vcomh Deselect level
     VcomhDeselect(VcomhLevel),
+    /// Set up continuous horizontal and vertical scrolling. Takes effect once scrolling is
+    /// activated.
+    SetupScroll {
+        /// Number of columns to scroll horizontally each step, 0 for no horizontal scroll
+        h_offset: u8,
+        /// First row of the horizontally scrolled area
+        start_row: u8,
+        /// Number of rows in the horizontally scrolled area
+        num_rows: u8,
+        /// Number of rows to scroll vertically each step, 0 for no vertical scroll
+        v_offset: u8,
+        /// Time between each scroll step
+        interval: NFrames,
+    },
+    /// Start continuous scrolling as set up by `SetupScroll`
+    ActivateScroll,
+    /// Stop any active continuous scroll
+    DeactivateScroll,
+    /// Draw a line from (column, row) to (column, row) in the given RGB565 colour
+    DrawLine(u8, u8, u8, u8, u16),
+    /// Draw a rectangle from (column, row) to (column, row) with the given RGB565 outline and
+    /// fill colours. The fill colour is only used if filling is enabled with `FillMode`.
+    DrawRect(u8, u8, u8, u8, u16, u16),
+    /// Copy the window from (column, row) to (column, row) so its top left corner is at
+    /// (column, row)
+    CopyWindow(u8, u8, u8, u8, u8, u8),
+    /// Clear the window from (column, row) to (column, row) to black
+    ClearWindow(u8, u8, u8, u8),
+    /// Enable or disable filling rectangles drawn with `DrawRect`
+    FillMode(bool),
+    /// Set the gray scale table, the pulse width from 0-125 for each of 32 gray levels
+    GrayScaleTable([u8; 32]),
+    /// Reset the gray scale table to the built in linear table
+    LinearGrayScale,
     /// NOOP
     Noop,
 }
 
 impl Command {
+    /// Encode the command as the bytes sent to the SSD1331, returning a fixed size array and the
+    /// number of bytes used
+    pub fn encode(&self) -> ([u8; MAX_COMMAND_LEN], usize) {
+        match *self {
+            Command::Contrast(a, b, c) => encoded(&[0x81, a, 0x82, b, 0x83, c]),
+            Command::MasterCurrent(current) => encoded(&[0x87, 0xF & current]),
+            Command::DisplayMode(mode) => encoded(&[mode as u8]),
+            Command::DisplayOn(on) => encoded(&[0xAE | (on as u8)]),
+            Command::ColumnAddress(start, end) => encoded(&[0x15, start, end]),
+            Command::RowAddress(start, end) => encoded(&[0x75, start, end]),
+            Command::StartLine(line) => encoded(&[0xA1, 0x3F & line]),
+            Command::RemapAndColorDepth(hremap, vremap, cmode, addr_inc_mode) => encoded(&[
+                0xA0,
+                // Bit 5 enables COM split odd/even, which the panel is wired for
+                0x20 | (cmode as u8) << 6
+                    | (vremap as u8) << 4
+                    | (hremap as u8) << 1
+                    | (addr_inc_mode as u8),
+            ]),
+            Command::Multiplex(ratio) => encoded(&[0xA8, ratio]),
+            Command::DisplayOffset(offset) => encoded(&[0xA2, offset]),
+            Command::DisplayClockDiv(fosc, div) => {
+                encoded(&[0xB3, ((0xF & fosc) << 4) | (0xF & div)])
+            }
+            Command::PreChargePeriod(phase1, phase2) => {
+                encoded(&[0xB1, ((0xF & phase2) << 4) | (0xF & phase1)])
+            }
+            Command::PreChargeVoltage(level) => encoded(&[0xBB, (0x1F & level) << 1]),
+            Command::VcomhDeselect(level) => encoded(&[0xBE, (level as u8) << 1]),
+            Command::SetupScroll {
+                h_offset,
+                start_row,
+                num_rows,
+                v_offset,
+                interval,
+            } => encoded(&[
+                0x27,
+                h_offset,
+                0x3F & start_row,
+                0x7F & num_rows,
+                0x3F & v_offset,
+                interval as u8,
+            ]),
+            Command::ActivateScroll => encoded(&[0x2F]),
+            Command::DeactivateScroll => encoded(&[0x2E]),
+            Command::DrawLine(c1, r1, c2, r2, color) => {
+                let (r, g, b) = raw16_to_ssd1331_accel(color);
+
+                encoded(&[0x21, c1, r1, c2, r2, r, g, b])
+            }
+            Command::DrawRect(c1, r1, c2, r2, line, fill) => {
+                let (lr, lg, lb) = raw16_to_ssd1331_accel(line);
+                let (fr, fg, fb) = raw16_to_ssd1331_accel(fill);
+
+                encoded(&[0x22, c1, r1, c2, r2, lr, lg, lb, fr, fg, fb])
+            }
+            Command::CopyWindow(c1, r1, c2, r2, c3, r3) => encoded(&[0x23, c1, r1, c2, r2, c3, r3]),
+            Command::ClearWindow(c1, r1, c2, r2) => encoded(&[0x25, c1, r1, c2, r2]),
+            Command::FillMode(fill) => encoded(&[0x26, fill as u8]),
+            Command::GrayScaleTable(table) => {
+                let mut data = [0; MAX_COMMAND_LEN];
+
+                data[0] = 0xB8;
+
+                for (out, width) in data[1..].iter_mut().zip(table.iter()) {
+                    *out = 0x7F & width;
+                }
+
+                (data, MAX_COMMAND_LEN)
+            }
+            Command::LinearGrayScale => encoded(&[0xB9]),
+            Command::Noop => encoded(&[0xE3]),
+        }
+    }
+
     /// Send command to SSD1331
     pub fn send<SPI, DC, CommE, PinE>(
         self,
@@ -60,56 +168,7 @@ impl Command {
         SPI: hal::blocking::spi::Write<u8, Error = CommE>,
         DC: OutputPin<Error = PinE>,
     {
-        // Transform command into a fixed size array of 7 u8 and the real length for sending
-        let (data, len) = match self {
-            Command::Contrast(a, b, c) => ([0x81, a, 0x82, b, 0x83, c, 0], 6),
-            // TODO: Collapse AllOn and Invert commands into new DisplayMode cmd with enum
-            Command::AllOn(on) => ([if on { 0xA5 } else { 0xA6 }, 0, 0, 0, 0, 0, 0], 1),
-            Command::Invert(inv) => ([if inv { 0xA7 } else { 0xA4 }, 0, 0, 0, 0, 0, 0], 1),
-            Command::DisplayOn(on) => ([0xAE | (on as u8), 0, 0, 0, 0, 0, 0], 1),
-            Command::ColumnAddress(start, end) => ([0x15, start, end, 0, 0, 0, 0], 3),
-            Command::RowAddress(start, end) => ([0x75, start, end, 0, 0, 0, 0], 3),
-            Command::StartLine(line) => ([0xA1, (0x3F & line), 0, 0, 0, 0, 0], 2),
-            Command::RemapAndColorDepth(hremap, vremap, cmode, addr_inc_mode) => (
-                [
-                    0xA0,
-                    0x20 | ((vremap as u8) << 4
-                        | (hremap as u8) << 1
-                        | (cmode as u8) << 6
-                        | (addr_inc_mode as u8)),
-                    0,
-                    0,
-                    0,
-                    0,
-                    0,
-                ],
-                2,
-            ),
-            Command::Multiplex(ratio) => ([0xA8, ratio, 0, 0, 0, 0, 0], 2),
-            Command::ReverseComDir(rev) => ([0xC0 | ((rev as u8) << 3), 0, 0, 0, 0, 0, 0], 1),
-            Command::DisplayOffset(offset) => ([0xA2, offset, 0, 0, 0, 0, 0], 2),
-            Command::ComPinConfig(alt, lr) => (
-                [
-                    0xDA,
-                    0x2 | ((alt as u8) << 4) | ((lr as u8) << 5),
-                    0,
-                    0,
-                    0,
-                    0,
-                    0,
-                ],
-                2,
-            ),
-            Command::DisplayClockDiv(fosc, div) => {
-                ([0xB3, ((0xF & fosc) << 4) | (0xF & div), 0, 0, 0, 0, 0], 2)
-            }
-            Command::PreChargePeriod(phase1, phase2) => (
-                [0x3e, ((0xF & phase2) << 4) | (0xF & phase1), 0, 0, 0, 0, 0],
-                2,
-            ),
-            Command::VcomhDeselect(level) => ([0xBE, (level as u8) << 1, 0, 0, 0, 0, 0], 2),
-            Command::Noop => ([0xE3, 0, 0, 0, 0, 0, 0], 1),
-        };
+        let (data, len) = self.encode();
 
         // Command mode. 1 = data, 0 = command
         dc.set_low().map_err(Error::Pin)?;
@@ -119,6 +178,27 @@ impl Command {
     }
 }
 
+/// Copy command bytes into the fixed size array returned by `Command::encode`
+fn encoded(bytes: &[u8]) -> ([u8; MAX_COMMAND_LEN], usize) {
+    let mut data = [0; MAX_COMMAND_LEN];
+
+    data[0..bytes.len()].copy_from_slice(bytes);
+
+    (data, bytes.len())
+}
+
+/// Convert an RGB565 colour into the 6 bit per channel (r, g, b) values used by the accelerated
+/// drawing commands
+///
+/// Red and blue only have 5 bits of precision, so are shifted up by one to fill the 6 bit range.
+pub fn raw16_to_ssd1331_accel(color: u16) -> (u8, u8, u8) {
+    let r = ((color >> 11) as u8 & 0x1F) << 1;
+    let g = (color >> 5) as u8 & 0x3F;
+    let b = (color as u8 & 0x1F) << 1;
+
+    (r, g, b)
+}
+
 /// Horizontal Scroll Direction
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
@@ -139,30 +219,43 @@ pub enum VHScrollDir {
     VerticalLeft = 0b10,
 }
 
-/// Frame interval
+/// Frame interval between scroll steps
+///
+/// Used by [`Ssd1331::start_scroll`](./struct.Ssd1331.html#method.start_scroll).
 #[derive(Debug, Clone, Copy)]
-#[allow(dead_code)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum NFrames {
-    /// 2 Frames
-    F2 = 0b111,
-    /// 3 Frames
-    F3 = 0b100,
-    /// 4 Frames
-    F4 = 0b101,
-    /// 5 Frames
-    F5 = 0b000,
-    /// 25 Frames
-    F25 = 0b110,
-    /// 64 Frames
-    F64 = 0b001,
-    /// 128 Frames
-    F128 = 0b010,
-    /// 256 Frames
-    F256 = 0b011,
+    /// 6 Frames
+    F6 = 0b00,
+    /// 10 Frames
+    F10 = 0b01,
+    /// 100 Frames
+    F100 = 0b10,
+    /// 200 Frames
+    F200 = 0b11,
 }
 
 /// Vcomh Deselect level
-#[derive(Debug, Clone, Copy)]
+///
+/// Each level converts to and from a byte with `u8::from` and `VcomhLevel::try_from`, for example
+/// to store the configuration in flash. The byte values are `0x00`, `0x08`, `0x10`, `0x18` and
+/// `0x1F`, matching the order of the variants. Converting any other byte returns an
+/// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::convert::TryFrom;
+/// use ssd1331::VcomhLevel;
+///
+/// let stored = u8::from(VcomhLevel::V061);
+///
+/// assert_eq!(stored, 0x10);
+/// assert_eq!(VcomhLevel::try_from(stored).unwrap(), VcomhLevel::V061);
+/// assert!(VcomhLevel::try_from(0x01).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 pub enum VcomhLevel {
     /// 0.44 * Vcc
@@ -177,8 +270,62 @@ pub enum VcomhLevel {
     V083 = 0b11111,
 }
 
+impl From<VcomhLevel> for u8 {
+    fn from(level: VcomhLevel) -> Self {
+        level as u8
+    }
+}
+
+impl TryFrom<u8> for VcomhLevel {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0b00000 => Ok(VcomhLevel::V044),
+            0b01000 => Ok(VcomhLevel::V052),
+            0b10000 => Ok(VcomhLevel::V061),
+            0b11000 => Ok(VcomhLevel::V071),
+            0b11111 => Ok(VcomhLevel::V083),
+            _ => Err(Error::InvalidArgument),
+        }
+    }
+}
+
+/// Display mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum DisplayMode {
+    /// Show the contents of display memory
+    Normal = 0xA4,
+    /// Turn every pixel on at full brightness, ignoring display memory
+    AllOn = 0xA5,
+    /// Turn every pixel off, ignoring display memory
+    AllOff = 0xA6,
+    /// Show the contents of display memory with inverted colours
+    Inverse = 0xA7,
+}
+
 /// Color mode
-#[derive(Debug, Clone, Copy)]
+///
+/// Each mode converts to and from a byte with `u8::from` and `ColorMode::try_from`, for example to
+/// store the configuration in flash. The byte values are `0x00` for 256 colours and `0x01` for 65k
+/// colours. Converting any other byte returns an
+/// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::convert::TryFrom;
+/// use ssd1331::ColorMode;
+///
+/// let stored = u8::from(ColorMode::CM65k);
+///
+/// assert_eq!(stored, 0x01);
+/// assert_eq!(ColorMode::try_from(stored).unwrap(), ColorMode::CM65k);
+/// assert!(ColorMode::try_from(0x02).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 pub enum ColorMode {
     /// 256 colors per pixel
@@ -188,6 +335,24 @@ pub enum ColorMode {
     CM65k = 0x01,
 }
 
+impl From<ColorMode> for u8 {
+    fn from(mode: ColorMode) -> Self {
+        mode as u8
+    }
+}
+
+impl TryFrom<u8> for ColorMode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(ColorMode::CM256),
+            0x01 => Ok(ColorMode::CM65k),
+            _ => Err(Error::InvalidArgument),
+        }
+    }
+}
+
 /// Address increment mode
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
@@ -198,3 +363,160 @@ pub enum AddressIncrementMode {
     /// Vertical address increment
     Vertical = 0x01,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{CaptureSpi, Pin};
+
+    #[test]
+    fn setup_scroll() {
+        let mut out = [0u8; 8];
+        let mut spi = CaptureSpi::new(&mut out);
+
+        Command::SetupScroll {
+            h_offset: 1,
+            start_row: 8,
+            num_rows: 48,
+            v_offset: 2,
+            interval: NFrames::F100,
+        }
+        .send(&mut spi, &mut Pin)
+        .unwrap();
+
+        assert_eq!(spi.written(), [0x27, 0x01, 0x08, 0x30, 0x02, 0x02]);
+    }
+
+    #[test]
+    fn encode_matches_datasheet() {
+        let cases: [(Command, &[u8]); 33] = [
+            (
+                Command::Contrast(0x91, 0x50, 0x7D),
+                &[0x81, 0x91, 0x82, 0x50, 0x83, 0x7D],
+            ),
+            (Command::MasterCurrent(0x06), &[0x87, 0x06]),
+            (Command::MasterCurrent(0x1F), &[0x87, 0x0F]),
+            (Command::DisplayMode(DisplayMode::Normal), &[0xA4]),
+            (Command::DisplayMode(DisplayMode::AllOn), &[0xA5]),
+            (Command::DisplayMode(DisplayMode::AllOff), &[0xA6]),
+            (Command::DisplayMode(DisplayMode::Inverse), &[0xA7]),
+            (Command::DisplayOn(false), &[0xAE]),
+            (Command::DisplayOn(true), &[0xAF]),
+            (Command::ColumnAddress(2, 95), &[0x15, 2, 95]),
+            (Command::RowAddress(3, 63), &[0x75, 3, 63]),
+            (Command::StartLine(0x7F), &[0xA1, 0x3F]),
+            (
+                Command::RemapAndColorDepth(
+                    false,
+                    false,
+                    ColorMode::CM65k,
+                    AddressIncrementMode::Horizontal,
+                ),
+                &[0xA0, 0x60],
+            ),
+            (
+                Command::RemapAndColorDepth(
+                    true,
+                    true,
+                    ColorMode::CM256,
+                    AddressIncrementMode::Vertical,
+                ),
+                &[0xA0, 0x33],
+            ),
+            (Command::Multiplex(63), &[0xA8, 0x3F]),
+            (Command::DisplayOffset(8), &[0xA2, 8]),
+            (Command::DisplayClockDiv(0xF, 0x0), &[0xB3, 0xF0]),
+            (Command::PreChargePeriod(0x1, 0xF), &[0xB1, 0xF1]),
+            (Command::PreChargeVoltage(0x1D), &[0xBB, 0x3A]),
+            (Command::PreChargeVoltage(0xFF), &[0xBB, 0x3E]),
+            (Command::VcomhDeselect(VcomhLevel::V044), &[0xBE, 0x00]),
+            (Command::VcomhDeselect(VcomhLevel::V083), &[0xBE, 0x3E]),
+            (
+                Command::SetupScroll {
+                    h_offset: 1,
+                    start_row: 0x48,
+                    num_rows: 0xC0,
+                    v_offset: 0x42,
+                    interval: NFrames::F200,
+                },
+                &[0x27, 0x01, 0x08, 0x40, 0x02, 0x03],
+            ),
+            (Command::ActivateScroll, &[0x2F]),
+            (Command::DeactivateScroll, &[0x2E]),
+            (
+                Command::DrawLine(0, 1, 95, 63, 0xf81f),
+                &[0x21, 0, 1, 95, 63, 0x3e, 0x00, 0x3e],
+            ),
+            (
+                Command::DrawRect(0, 0, 95, 63, 0x07e0, 0xffff),
+                &[0x22, 0, 0, 95, 63, 0x00, 0x3f, 0x00, 0x3e, 0x3f, 0x3e],
+            ),
+            (
+                Command::CopyWindow(1, 2, 30, 40, 0, 3),
+                &[0x23, 1, 2, 30, 40, 0, 3],
+            ),
+            (Command::ClearWindow(1, 2, 30, 40), &[0x25, 1, 2, 30, 40]),
+            (Command::FillMode(true), &[0x26, 0x01]),
+            (Command::FillMode(false), &[0x26, 0x00]),
+            (Command::LinearGrayScale, &[0xB9]),
+            (Command::Noop, &[0xE3]),
+        ];
+
+        for (command, expected) in cases.iter() {
+            let (data, len) = command.encode();
+
+            assert_eq!(data[0..len], **expected, "{:?}", command);
+            assert!(data[len..].iter().all(|b| *b == 0), "{:?}", command);
+        }
+    }
+
+    #[test]
+    fn gray_scale_table() {
+        let mut table = [0u8; 32];
+
+        for (n, width) in table.iter_mut().enumerate() {
+            *width = n as u8 * 4;
+        }
+
+        // Out of range widths are masked to 7 bits
+        table[31] = 0xFD;
+
+        let (data, len) = Command::GrayScaleTable(table).encode();
+
+        assert_eq!(len, 33);
+        assert_eq!(data[0], 0xB8);
+        assert_eq!(data[1..31], table[0..30]);
+        assert_eq!(data[31..33], [120, 0x7D]);
+    }
+
+    #[test]
+    fn byte_round_trip() {
+        let levels = [
+            VcomhLevel::V044,
+            VcomhLevel::V052,
+            VcomhLevel::V061,
+            VcomhLevel::V071,
+            VcomhLevel::V083,
+        ];
+
+        for value in 0..=u8::MAX {
+            match VcomhLevel::try_from(value) {
+                Ok(level) => assert_eq!(u8::from(level), value),
+                Err(e) => assert!(matches!(e, Error::InvalidArgument)),
+            }
+
+            match ColorMode::try_from(value) {
+                Ok(mode) => assert_eq!(u8::from(mode), value),
+                Err(e) => assert!(matches!(e, Error::InvalidArgument)),
+            }
+        }
+
+        for level in levels.iter() {
+            assert_eq!(VcomhLevel::try_from(u8::from(*level)).unwrap(), *level);
+        }
+
+        for mode in [ColorMode::CM256, ColorMode::CM65k].iter() {
+            assert_eq!(ColorMode::try_from(u8::from(*mode)).unwrap(), *mode);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    command::{ColorMode, VcomhLevel},
+    displayrotation::DisplayRotation,
+    DISPLAY_HEIGHT,
+};
+
+/// Snapshot of the controller settings tracked by the driver
+///
+/// The SSD1331 can't be read from over SPI, so these are the values the driver has sent (or will
+/// send during `init()`), not values read back from the chip. They can be logged to help diagnose
+/// differences between expected and visible output, for example when a command was lost on the
+/// bus or the panel was reset without the driver knowing.
+///
+/// Returned by [`Ssd1331::config_snapshot`](./struct.Ssd1331.html#method.config_snapshot).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Display rotation
+    pub rotation: DisplayRotation,
+
+    /// Whether the rotation is applied in software rather than by the controller's remap
+    pub software_rotation: bool,
+
+    /// Per channel (r, g, b) contrast at full brightness
+    pub contrast: (u8, u8, u8),
+
+    /// Overall brightness, split between master current and contrast when sent to the display
+    pub brightness: u8,
+
+    /// Whether the display is inverted
+    pub inverted: bool,
+
+    /// Whether the display is blanked, showing every pixel off regardless of display memory
+    pub blanked: bool,
+
+    /// Whether every pixel is lit at full brightness regardless of display memory
+    pub all_on: bool,
+
+    /// Whether the display is on
+    pub display_on: bool,
+
+    /// Colour depth
+    pub color_mode: ColorMode,
+
+    /// Phase 1 and phase 2 precharge periods
+    pub precharge_period: (u8, u8),
+
+    /// Precharge voltage level from 0 to 31, or `None` to leave the controller's reset default
+    pub precharge_voltage: Option<u8>,
+
+    /// Vcomh deselect level
+    pub vcomh: VcomhLevel,
+
+    /// Multiplex ratio, one less than the number of GDDRAM rows driven
+    pub multiplex: u8,
+
+    /// GDDRAM row shown at the top of the display
+    pub start_line: u8,
+
+    /// Vertical shift applied to the display rows
+    pub display_offset: u8,
+
+    /// Oscillator frequency and display clock divide ratio minus one, each from 0 to 15
+    ///
+    /// The default of `(0xF, 0x0)` runs the oscillator at its fastest with no division, for the
+    /// highest refresh rate.
+    pub display_clock: (u8, u8),
+
+    /// Gray scale table, or `None` to use the built in linear table
+    pub gamma: Option<[u8; 32]>,
+}
+
+impl Config {
+    /// Settings applied by `init()`
+    pub(crate) fn new(rotation: DisplayRotation) -> Self {
+        Self {
+            rotation,
+            software_rotation: false,
+            // Values taken from [here](https://github.com/adafruit/Adafruit-SSD1331-OLED-Driver-Library-for-Arduino/blob/master/Adafruit_SSD1331.cpp#L119-L124)
+            contrast: (0x91, 0x50, 0x7D),
+            brightness: 255,
+            inverted: false,
+            blanked: false,
+            all_on: false,
+            display_on: false,
+            color_mode: ColorMode::CM65k,
+            precharge_period: (0x1, 0xF),
+            precharge_voltage: None,
+            vcomh: VcomhLevel::V071,
+            multiplex: DISPLAY_HEIGHT - 1,
+            start_line: 0,
+            display_offset: 0,
+            display_clock: (0xF, 0x0),
+            gamma: None,
+        }
+    }
+}
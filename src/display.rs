@@ -1,14 +1,146 @@
+use core::ops::Range;
+
 use hal::{blocking::delay::DelayMs, digital::v2::OutputPin};
 
 use crate::{
-    command::{AddressIncrementMode, ColorMode, Command, VcomhLevel},
+    boundspolicy::BoundsPolicy,
+    byteorder::ByteOrder,
+    command::{AddressIncrementMode, ColorMode, Command, DisplayMode, NFrames, VcomhLevel},
+    config::Config,
     displayrotation::DisplayRotation,
+    drawarea::DrawArea,
     error::Error,
-    DISPLAY_HEIGHT, DISPLAY_WIDTH,
+    framebuffer::FrameBuffer,
+    op::Op,
+    DISPLAY_HEIGHT, DISPLAY_WIDTH, FRAME_HEIGHT, FRAME_WIDTH, STRIDE_BYTES,
 };
 
 /// 96px x 64px screen with 16 bits (2 bytes) per pixel
-const BUF_SIZE: usize = 96 * 64 * 2;
+pub(crate) const BUF_SIZE: usize = STRIDE_BYTES * FRAME_HEIGHT as usize;
+
+/// 96px x 64px screen with 8 bits (1 byte) per pixel, for 256 colour mode
+pub(crate) const BUF_SIZE_256: usize = FRAME_WIDTH as usize * FRAME_HEIGHT as usize;
+
+/// Column and row address commands setting the draw area to the full display
+const FULL_DRAW_AREA: [u8; 6] = [0x15, 0, DISPLAY_WIDTH - 1, 0x75, 0, DISPLAY_HEIGHT - 1];
+
+/// Number of bytes sent between calls to the closure passed to `flush_yielding`
+const YIELD_CHUNK_SIZE: usize = 1024;
+
+/// Time for the controller to fill the whole display with an accelerated rectangle
+#[cfg(feature = "graphics")]
+const ACCEL_FILL_DELAY_MS: u8 = 3;
+
+/// Settling time after setting the master current in `init_with_delay`
+const CURRENT_SETTLE_MS: u8 = 10;
+
+/// Time for the segment and common drivers to turn on after the display on command
+const DISPLAY_ON_DELAY_MS: u8 = 100;
+
+/// Number of brightness steps `shutdown` fades out over
+const SHUTDOWN_FADE_STEPS: u8 = 10;
+
+/// Time `release_blanked` waits after turning the display off, longer than one frame at the
+/// default display clock
+const RELEASE_BLANK_DELAY_MS: u8 = 20;
+
+/// Time each flash of `init_with_self_test` stays on and off
+const SELF_TEST_FLASH_MS: u8 = 200;
+
+/// Delay between each brightness step of `turn_on_faded` and `turn_off_faded`
+const FADE_STEP_MS: u8 = 10;
+
+/// Offsets applied by successive calls to `antiburn_tick`, returning to the start after a cycle
+const ANTIBURN_PATTERN: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// 4x4 Bayer threshold matrix used to dither pixels in 256 colour mode, indexed by `[y % 4][x % 4]`
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Sine of 0 to 90 degrees in 1 degree steps, scaled to 0-255
+const SIN_TABLE: [u8; 91] = [
+    0, 4, 9, 13, 18, 22, 27, 31, 35, 40, 44, 49, 53, 57, 62, 66, 70, 75, 79, 83, 87, 91, 96, 100,
+    104, 108, 112, 116, 120, 124, 127, 131, 135, 139, 143, 146, 150, 153, 157, 160, 164, 167, 171,
+    174, 177, 180, 183, 186, 190, 192, 195, 198, 201, 204, 206, 209, 211, 214, 216, 219, 221, 223,
+    225, 227, 229, 231, 233, 235, 236, 238, 240, 241, 243, 244, 245, 246, 247, 248, 249, 250, 251,
+    252, 253, 253, 254, 254, 254, 255, 255, 255, 255,
+];
+
+/// Write `data` to the SPI bus in writes of at most `max_len` bytes
+fn write_chunked<SPI, CommE, PinE>(
+    spi: &mut SPI,
+    data: &[u8],
+    max_len: usize,
+) -> Result<(), Error<CommE, PinE>>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+{
+    for data in data.chunks(max_len) {
+        spi.write(data).map_err(Error::Comm)?;
+    }
+
+    Ok(())
+}
+
+/// Convert an RGB565 value to the controller's 256 colour RGB332 format by truncation
+fn rgb565_to_rgb332(value: u16) -> u8 {
+    let r = (value >> 13) as u8 & 0x07;
+    let g = (value >> 8) as u8 & 0x07;
+    let b = (value >> 3) as u8 & 0x03;
+
+    (r << 5) | (g << 2) | b
+}
+
+/// Convert an RGB565 value to RGB332 with ordered dithering for the pixel at `(x, y)`
+///
+/// Each channel has a threshold from [`BAYER_4X4`] added, scaled to the bits dropped by
+/// truncation, before being truncated. Channels saturate rather than wrapping, so full intensity
+/// stays full intensity.
+fn rgb565_to_rgb332_dithered(value: u16, x: usize, y: usize) -> u8 {
+    let threshold = u16::from(BAYER_4X4[y % 4][x % 4]);
+
+    // Red drops 2 of its 5 bits, green 3 of 6 and blue 3 of 5
+    let r = ((value >> 11) + threshold / 4).min(0x1f);
+    let g = (((value >> 5) & 0x3f) + threshold / 2).min(0x3f);
+    let b = ((value & 0x1f) + threshold / 2).min(0x1f);
+
+    rgb565_to_rgb332((r << 11) | (g << 5) | b)
+}
+
+/// Expand an RGB332 value to RGB565, repeating the high bits to fill each channel
+fn rgb332_to_rgb565(value: u8) -> u16 {
+    let r = u16::from(value >> 5);
+    let g = u16::from((value >> 2) & 0x07);
+    let b = u16::from(value & 0x03);
+
+    let r = (r << 2) | (r >> 1);
+    let g = (g << 3) | g;
+    let b = (b << 3) | (b << 1) | (b >> 1);
+
+    (r << 11) | (g << 5) | b
+}
+
+/// Sine of an angle in degrees, scaled to -255 to 255
+fn sin_deg(angle: u16) -> i32 {
+    let angle = usize::from(angle % 360);
+
+    match angle {
+        0..=90 => i32::from(SIN_TABLE[angle]),
+        91..=180 => i32::from(SIN_TABLE[180 - angle]),
+        181..=270 => -i32::from(SIN_TABLE[angle - 180]),
+        _ => -i32::from(SIN_TABLE[360 - angle]),
+    }
+}
+
+/// Integer square root, rounded down
+fn isqrt(n: u32) -> u32 {
+    let mut root = 0;
+
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+
+    root
+}
 
 /// SSD1331 display interface
 ///
@@ -83,14 +215,60 @@ const BUF_SIZE: usize = 96 * 64 * 2;
 /// ```
 ///
 /// [`embedded-graphics`]: https://crates.io/crates/embedded-graphics
-pub struct Ssd1331<SPI, DC> {
+///
+/// ## Framebuffer storage
+///
+/// By default the display owns its 12,288 byte framebuffer, as in [`Ssd1331Owned`]. The `B` type
+/// parameter allows other [`FrameBuffer`] storage to be used, such as a slice borrowed with
+/// [`with_buffer`](#method.with_buffer) as an [`Ssd1331Borrowed`], or the 6,144 byte buffer of
+/// [`Ssd1331Owned256`] for 256 colour mode. All other methods work the same whichever storage is
+/// used.
+///
+/// [`Ssd1331Owned`]: ./type.Ssd1331Owned.html
+/// [`Ssd1331Owned256`]: ./type.Ssd1331Owned256.html
+/// [`Ssd1331Borrowed`]: ./type.Ssd1331Borrowed.html
+/// [`FrameBuffer`]: ./trait.FrameBuffer.html
+pub struct Ssd1331<SPI, DC, B = [u8; BUF_SIZE]> {
     /// Pixel buffer
     ///
-    /// The display is 16BPP RGB565, so two `u8`s are used for each pixel value
-    buffer: [u8; BUF_SIZE],
+    /// In 65k colour mode the display is 16BPP RGB565, so two `u8`s are used for each pixel value,
+    /// stored in `byte_order`. In 256 colour mode each pixel is one RGB332 `u8`, and only the first
+    /// half of the buffer is used.
+    buffer: B,
+
+    /// Controller settings, including which display rotation to use
+    config: Config,
+
+    /// Byte order of RGB565 pixels in the framebuffer
+    byte_order: ByteOrder,
 
-    /// Which display rotation to use
-    display_rotation: DisplayRotation,
+    /// Whether `flush` may skip setting the draw area when it is known to be unchanged
+    fast_flush: bool,
+
+    /// Largest number of framebuffer bytes sent in a single SPI write
+    max_transfer_size: usize,
+
+    /// Whether `init` clears the controller's memory before turning the display on
+    clear_on_init: bool,
+
+    /// Whether pixels drawn into the framebuffer in 256 colour mode are dithered
+    dithering: bool,
+
+    /// Whether the controller's draw area is known to be the full display, with the address
+    /// pointer at its start
+    window_full: bool,
+
+    /// How out of range coordinates passed to `set_draw_area` are handled
+    bounds_policy: BoundsPolicy,
+
+    /// Number of full frames sent, wrapping on overflow
+    frame_count: u32,
+
+    /// Inclusive logical corners of the region changed since it was last sent, if any
+    dirty: Option<((u8, u8), (u8, u8))>,
+
+    /// Position in `ANTIBURN_PATTERN` of the next `antiburn_tick` shift
+    antiburn_step: u8,
 
     /// SPI interface
     spi: SPI,
@@ -99,6 +277,24 @@ pub struct Ssd1331<SPI, DC> {
     dc: DC,
 }
 
+/// Display interface that owns its framebuffer
+///
+/// This is the same type as `Ssd1331<SPI, DC>`.
+pub type Ssd1331Owned<SPI, DC> = Ssd1331<SPI, DC, [u8; BUF_SIZE]>;
+
+/// Display interface that owns a framebuffer only large enough for 256 colour mode
+///
+/// Created with [`Ssd1331::new_256`](./struct.Ssd1331.html#method.new_256).
+pub type Ssd1331Owned256<SPI, DC> = Ssd1331<SPI, DC, [u8; BUF_SIZE_256]>;
+
+/// Display interface using a framebuffer borrowed from elsewhere
+///
+/// Created with [`Ssd1331::with_buffer`](./struct.Ssd1331.html#method.with_buffer). The slice
+/// must be at least 12,288 bytes long, or 6,144 bytes if created with
+/// [`Ssd1331::with_buffer_256`](./struct.Ssd1331.html#method.with_buffer_256). Shorter slices are
+/// rejected when the display is created.
+pub type Ssd1331Borrowed<'a, SPI, DC> = Ssd1331<SPI, DC, &'a mut [u8]>;
+
 impl<SPI, DC, CommE, PinE> Ssd1331<SPI, DC>
 where
     SPI: hal::blocking::spi::Write<u8, Error = CommE>,
@@ -130,281 +326,6839 @@ where
     /// display.flush().unwrap();
     /// ```
     pub fn new(spi: SPI, dc: DC, display_rotation: DisplayRotation) -> Self {
-        Self {
-            spi,
-            dc,
-            display_rotation,
-            buffer: [0; BUF_SIZE],
-        }
+        Self::from_buffer(spi, dc, display_rotation, [0; BUF_SIZE])
     }
+}
 
-    /// Release SPI and DC resources for reuse in other code
-    pub fn release(self) -> (SPI, DC) {
-        (self.spi, self.dc)
+impl<SPI, DC, CommE, PinE> Ssd1331<SPI, DC, [u8; BUF_SIZE_256]>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+{
+    /// Create new display instance in 256 colour mode
+    ///
+    /// Ensure `display.init()` is called before sending data otherwise nothing will be shown.
+    ///
+    /// Each pixel is stored as a single RGB332 byte, so the driver allocates a buffer of 96px *
+    /// 64px * 8bits = 6,144 bytes, half that of [`new`](#method.new). RGB565 values passed to
+    /// [`set_pixel`](#method.set_pixel) and drawn with `embedded-graphics` are truncated to 3 bits
+    /// of red and green and 2 bits of blue, and flushes send half as much data.
+    ///
+    /// The buffer is too small for 65k colours, so
+    /// [`init_with`](#method.init_with) returns an error if
+    /// [`ColorMode::CM65k`](./enum.ColorMode.html#variant.CM65k) is requested.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Pin, Spi};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new_256(spi, dc, Rotate0);
+    ///
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xf800);
+    /// display.flush().unwrap();
+    ///
+    /// assert_eq!(display.buffer_mut().len(), 96 * 64);
+    /// ```
+    pub fn new_256(spi: SPI, dc: DC, display_rotation: DisplayRotation) -> Self {
+        let mut display = Self::from_buffer(spi, dc, display_rotation, [0; BUF_SIZE_256]);
+
+        display.config.color_mode = ColorMode::CM256;
+
+        display
     }
+}
 
-    /// Clear the display buffer
+impl<'a, SPI, DC, CommE, PinE> Ssd1331<SPI, DC, &'a mut [u8]>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+{
+    /// Create new display instance using a framebuffer borrowed from elsewhere
     ///
-    /// `display.flush()` must be called to update the display
-    pub fn clear(&mut self) {
-        self.buffer = [0; BUF_SIZE];
+    /// Ensure `display.init()` is called before sending data otherwise nothing will be shown.
+    ///
+    /// [`new`](#method.new) stores its 12,288 byte framebuffer inline, which can overflow the
+    /// stack on some targets. This uses `buffer` instead, which can be placed in a static, a
+    /// particular linker section, or shared between displays that are created one after another.
+    /// It must be at least 12,288 bytes long, enough for a 65k colour frame. Use
+    /// [`with_buffer_256`](#method.with_buffer_256) for a half size buffer in 256 colour mode.
+    ///
+    /// The buffer's existing contents are used as the framebuffer, and are all sent by the next
+    /// [`flush_dirty`](#method.flush_dirty). Call [`clear`](#method.clear) to start from black.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Pin, Spi};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// // In firmware this would usually be a static
+    /// let mut buffer = [0u8; 96 * 64 * 2];
+    ///
+    /// let mut display = Ssd1331::with_buffer(spi, dc, Rotate0, &mut buffer);
+    ///
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xffff);
+    /// display.flush().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than 12,288 bytes.
+    pub fn with_buffer(
+        spi: SPI,
+        dc: DC,
+        display_rotation: DisplayRotation,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        Self::check_borrowed_len(buffer, BUF_SIZE);
+
+        Self::from_buffer(spi, dc, display_rotation, buffer)
     }
 
-    /// Reset the display
+    /// Create new display instance in 256 colour mode using a framebuffer borrowed from elsewhere
     ///
-    /// This method brings the RST pin low for 1ms to reset the module,  waits for another 1ms then
-    /// brings RST high
-    pub fn reset<RST, DELAY>(
-        &mut self,
-        rst: &mut RST,
-        delay: &mut DELAY,
-    ) -> Result<(), Error<CommE, PinE>>
+    /// This combines [`with_buffer`](#method.with_buffer) and [`new_256`](#method.new_256):
+    /// `buffer` only needs to be 6,144 bytes long, one byte per pixel, and
+    /// [`init_with`](#method.init_with) returns an error if 65k colours are requested.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Pin, Spi};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut buffer = [0u8; 96 * 64];
+    ///
+    /// let mut display = Ssd1331::with_buffer_256(spi, dc, Rotate0, &mut buffer);
+    ///
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xf800);
+    /// display.flush().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than 6,144 bytes.
+    pub fn with_buffer_256(
+        spi: SPI,
+        dc: DC,
+        display_rotation: DisplayRotation,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        Self::check_borrowed_len(buffer, BUF_SIZE_256);
+
+        let mut display = Self::from_buffer(spi, dc, display_rotation, buffer);
+
+        display.config.color_mode = ColorMode::CM256;
+
+        display
+    }
+
+    /// Panic if a borrowed framebuffer is too short, before it's used for anything
+    fn check_borrowed_len(buffer: &[u8], needed: usize) {
+        assert!(
+            buffer.len() >= needed,
+            "framebuffer is {} bytes, at least {} are needed",
+            buffer.len(),
+            needed
+        );
+    }
+}
+
+// Functions that don't act on a single display live here so they can be called as
+// `Ssd1331::function()` without naming the interface types
+impl Ssd1331<(), ()> {
+    /// Reset every display connected to a shared reset line
+    ///
+    /// When several panels share one active low reset line, pulsing it resets all of them at
+    /// once, so it should be driven once by whatever code coordinates the displays instead of
+    /// through [`reset`](#method.reset) on each one. This takes no display, and doesn't assume the
+    /// line belongs to any single panel: it brings `rst` low for 1ms, then high, and waits another
+    /// 1ms so every panel is ready to accept commands when it returns.
+    ///
+    /// The line's owner is expected to hold it high at all other times. Every display on the line
+    /// loses its settings and must be initialised again, for example with
+    /// [`init`](#method.init), before it is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interfaces, digital pins and delay. These are stub implementations used in
+    /// // examples.
+    /// let (spi_a, spi_b) = (Spi, Spi);
+    /// let (dc_a, dc_b) = (Pin, Pin);
+    /// let mut shared_rst = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut left = Ssd1331::new(spi_a, dc_a, Rotate0);
+    /// let mut right = Ssd1331::new(spi_b, dc_b, Rotate0);
+    ///
+    /// // Reset both panels with one pulse, then initialise each of them
+    /// Ssd1331::reset_shared(&mut shared_rst, &mut delay).unwrap();
+    ///
+    /// left.init().unwrap();
+    /// right.init().unwrap();
+    /// ```
+    pub fn reset_shared<RST, DELAY>(rst: &mut RST, delay: &mut DELAY) -> Result<(), RST::Error>
     where
-        RST: OutputPin<Error = PinE>,
+        RST: OutputPin,
         DELAY: DelayMs<u8>,
     {
-        rst.set_high().map_err(Error::Pin)?;
+        rst.set_low()?;
         delay.delay_ms(1);
-        rst.set_low().map_err(Error::Pin)?;
+        rst.set_high()?;
         delay.delay_ms(1);
-        rst.set_high().map_err(Error::Pin)?;
 
         Ok(())
     }
+}
 
-    /// Send the full framebuffer to the display
-    ///
-    /// This resets the draw area the full size of the display
-    pub fn flush(&mut self) -> Result<(), Error<CommE, PinE>> {
-        // Ensure the display buffer is at the origin of the display before we send the full frame
-        // to prevent accidental offsets
-        self.set_draw_area((0, 0), (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1))?;
-
-        // 1 = data, 0 = command
-        self.dc.set_high().map_err(Error::Pin)?;
+impl<SPI, DC, B, CommE, PinE> Ssd1331<SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+    B: FrameBuffer,
+{
+    /// Create a new display instance using the given framebuffer storage
+    fn from_buffer(spi: SPI, dc: DC, display_rotation: DisplayRotation, buffer: B) -> Self {
+        let mut display = Self {
+            spi,
+            dc,
+            config: Config::new(display_rotation),
+            buffer,
+            byte_order: ByteOrder::default(),
+            fast_flush: false,
+            max_transfer_size: usize::MAX,
+            clear_on_init: true,
+            dithering: false,
+            window_full: false,
+            bounds_policy: BoundsPolicy::default(),
+            frame_count: 0,
+            dirty: None,
+            antiburn_step: 0,
+        };
 
-        self.spi.write(&self.buffer).map_err(Error::Comm)?;
+        display.mark_all_dirty();
 
-        Ok(())
+        display
     }
 
-    /// Set the top left and bottom right corners of a bounding box to draw to
-    pub fn set_draw_area(
-        &mut self,
-        start: (u8, u8),
-        end: (u8, u8),
-    ) -> Result<(), Error<CommE, PinE>> {
-        Command::ColumnAddress(start.0, end.0).send(&mut self.spi, &mut self.dc)?;
-        Command::RowAddress(start.1.into(), (end.1).into()).send(&mut self.spi, &mut self.dc)?;
-        Ok(())
-    }
+    /// Set the per channel contrast used by [`init`](#method.init)
+    ///
+    /// This and the other analogue tuning builders,
+    /// [`with_master_current`](#method.with_master_current),
+    /// [`with_precharge_period`](#method.with_precharge_period),
+    /// [`with_precharge_voltage`](#method.with_precharge_voltage) and
+    /// [`with_vcomh`](#method.with_vcomh), only store their settings. Nothing is sent until
+    /// `init`, which sends them in this order after the address remap:
+    ///
+    /// 1. Contrast and master current
+    /// 2. Precharge period
+    /// 3. Precharge voltage
+    /// 4. Vcomh deselect level
+    ///
+    /// Settings that aren't set keep the defaults `init` has always used. Higher values give
+    /// higher contrast; the default is `(0x91, 0x50, 0x7D)`. This is the contrast at full
+    /// [brightness](#method.set_brightness).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331, VcomhLevel};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// // Calibration for this batch of panels
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0)
+    ///     .with_contrast(0x80, 0x48, 0x70)
+    ///     .with_master_current(0x0C)
+    ///     .with_precharge_period(0x2, 0xE)
+    ///     .with_precharge_voltage(0x1D)
+    ///     .with_vcomh(VcomhLevel::V083);
+    ///
+    /// display.init().unwrap();
+    /// ```
+    pub fn with_contrast(mut self, r: u8, g: u8, b: u8) -> Self {
+        self.config.contrast = (r, g, b);
 
-    /// Set the value for an individual pixel.
-    pub fn set_pixel(&mut self, x: u32, y: u32, value: u16) {
-        let idx = match self.display_rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                if x >= DISPLAY_WIDTH as u32 {
-                    return;
-                }
-                ((y as usize) * DISPLAY_WIDTH as usize) + (x as usize)
-            }
+        self
+    }
 
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                if y >= DISPLAY_WIDTH as u32 {
-                    return;
-                }
-                ((y as usize) * DISPLAY_HEIGHT as usize) + (x as usize)
-            }
-        } * 2;
+    /// Set the master current used by [`init`](#method.init), from 0 to 15
+    ///
+    /// Values above 15 are clamped to 15, the default. The master current and contrast are both
+    /// set through the [brightness](#method.set_brightness), so this sets the brightness that
+    /// sends `current` with the contrast unscaled. See
+    /// [`with_contrast`](#method.with_contrast) for when it is applied.
+    pub fn with_master_current(mut self, current: u8) -> Self {
+        self.config.brightness = current.min(15) * 16 + 15;
 
-        if idx >= self.buffer.len() - 1 {
-            return;
-        }
+        self
+    }
 
-        // Split 16 bit value into two bytes
-        let low = (value & 0xff) as u8;
-        let high = ((value & 0xff00) >> 8) as u8;
+    /// Set the phase 1 and phase 2 precharge periods used by [`init`](#method.init), each from 1
+    /// to 15 display clocks
+    ///
+    /// Only the low 4 bits of each are sent. The default is `(0x1, 0xF)`. See
+    /// [`with_contrast`](#method.with_contrast) for when it is applied.
+    pub fn with_precharge_period(mut self, phase1: u8, phase2: u8) -> Self {
+        self.config.precharge_period = (phase1, phase2);
 
-        self.buffer[idx] = high;
-        self.buffer[idx + 1] = low;
+        self
     }
 
-    /// Initialise display, setting sensible defaults and rotation
-    pub fn init(&mut self) -> Result<(), Error<CommE, PinE>> {
-        let display_rotation = self.display_rotation;
-
-        Command::DisplayOn(false).send(&mut self.spi, &mut self.dc)?;
-        Command::DisplayClockDiv(0xF, 0x0).send(&mut self.spi, &mut self.dc)?;
-        Command::Multiplex(DISPLAY_HEIGHT - 1).send(&mut self.spi, &mut self.dc)?;
-        Command::StartLine(0).send(&mut self.spi, &mut self.dc)?;
-        Command::DisplayOffset(0).send(&mut self.spi, &mut self.dc)?;
+    /// Set the precharge voltage used by [`init`](#method.init), from 0 (0.1 x Vcc) to 31 (0.5 x
+    /// Vcc)
+    ///
+    /// Values above 31 are clamped to 31. By default no precharge voltage is sent, leaving the
+    /// controller's reset value. See [`with_contrast`](#method.with_contrast) for when it is
+    /// applied.
+    pub fn with_precharge_voltage(mut self, level: u8) -> Self {
+        self.config.precharge_voltage = Some(level.min(31));
 
-        self.set_rotation(display_rotation)?;
+        self
+    }
 
-        // Values taken from [here](https://github.com/adafruit/Adafruit-SSD1331-OLED-Driver-Library-for-Arduino/blob/master/Adafruit_SSD1331.cpp#L119-L124)
-        Command::Contrast(0x91, 0x50, 0x7D).send(&mut self.spi, &mut self.dc)?;
-        Command::PreChargePeriod(0x1, 0xF).send(&mut self.spi, &mut self.dc)?;
-        Command::VcomhDeselect(VcomhLevel::V071).send(&mut self.spi, &mut self.dc)?;
-        Command::AllOn(false).send(&mut self.spi, &mut self.dc)?;
-        Command::Invert(false).send(&mut self.spi, &mut self.dc)?;
-        Command::DisplayOn(true).send(&mut self.spi, &mut self.dc)?;
+    /// Set the Vcomh deselect level used by [`init`](#method.init)
+    ///
+    /// The default is [`VcomhLevel::V071`](./enum.VcomhLevel.html#variant.V071). See
+    /// [`with_contrast`](#method.with_contrast) for when it is applied.
+    pub fn with_vcomh(mut self, level: VcomhLevel) -> Self {
+        self.config.vcomh = level;
 
-        Ok(())
+        self
     }
 
-    /// Get display dimensions, taking into account the current rotation of the display
+    /// Enable or disable clearing the controller's memory in [`init`](#method.init)
     ///
-    /// # Examples
+    /// Clearing is enabled by default. Disable it to save sending a frame during initialisation
+    /// when the first frame is sent some other way, for example with
+    /// [`init_off`](#method.init_off), or when the controller's memory is known to be valid. Use
+    /// [`set_clear_on_init`](#method.set_clear_on_init) to change it after the display is created.
     ///
-    /// ## No rotation
+    /// # Examples
     ///
     /// ```rust
     /// # use ssd1331::test_helpers::{Spi, Pin};
-    /// use ssd1331::{DisplayRotation, Ssd1331};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
     ///
     /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
     /// let spi = Spi;
     /// let dc = Pin;
     ///
-    /// let display = Ssd1331::new(spi, dc, DisplayRotation::Rotate0);
+    /// // The display was left running with valid contents, so don't clear it
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0).with_clear_on_init(false);
     ///
-    /// assert_eq!(display.dimensions(), (96, 64));
+    /// display.init().unwrap();
     /// ```
+    pub fn with_clear_on_init(mut self, enabled: bool) -> Self {
+        self.clear_on_init = enabled;
+
+        self
+    }
+
+    /// Release SPI and DC resources for reuse in other code
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+
+    /// Stop scrolling, turn the display off, then release SPI and DC resources
     ///
-    /// ## 90 degree rotation rotation
+    /// This leaves the panel in a clean state before the SPI bus is handed to another device.
+    /// The controller doesn't act on the display off command straight away: the frame being
+    /// scanned out when it arrives is finished first. After the commands are sent, `delay` is used
+    /// to wait 20ms, longer than a frame takes at the default display clock, so the display is
+    /// dark before the resources are released.
+    ///
+    /// The resources are always returned, so errors sending the commands are ignored. Call
+    /// [`turn_off`](#method.turn_off) first if they need to be handled. Use
+    /// [`release`](#method.release) to leave the panel running instead.
+    ///
+    /// # Examples
     ///
     /// ```rust
-    /// # use ssd1331::test_helpers::{Spi, Pin};
-    /// use ssd1331::{DisplayRotation, Ssd1331};
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
     ///
-    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
     /// let spi = Spi;
     /// let dc = Pin;
+    /// let mut delay = Delay;
     ///
-    /// let display = Ssd1331::new(spi, dc, DisplayRotation::Rotate90);
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
     ///
-    /// assert_eq!(display.dimensions(), (64, 96));
+    /// let (spi, dc) = display.release_blanked(&mut delay);
     /// ```
-    pub fn dimensions(&self) -> (u8, u8) {
-        match self.display_rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                (DISPLAY_WIDTH, DISPLAY_HEIGHT)
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                (DISPLAY_HEIGHT, DISPLAY_WIDTH)
-            }
-        }
+    pub fn release_blanked<DELAY>(mut self, delay: &mut DELAY) -> (SPI, DC)
+    where
+        DELAY: DelayMs<u8>,
+    {
+        Command::DeactivateScroll
+            .send(&mut self.spi, &mut self.dc)
+            .ok();
+        self.turn_off().ok();
+
+        delay.delay_ms(RELEASE_BLANK_DELAY_MS);
+
+        (self.spi, self.dc)
     }
 
-    /// Set the display rotation
-    pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error<CommE, PinE>> {
-        self.display_rotation = rot;
+    /// Fade the display out, turn it off, then release SPI and DC resources
+    ///
+    /// This is the clean teardown path for when the display is finished with, for example when a
+    /// task shuts down or the hardware is reconfigured at runtime. Any scroll is stopped, then the
+    /// display fades to black over 100ms with [`turn_off_faded`](#method.turn_off_faded) and is
+    /// turned off. If the fade fails part way, turning the display off is still attempted.
+    ///
+    /// Like [`release_blanked`](#method.release_blanked), the resources are always returned, so
+    /// errors sending the commands are ignored.
+    ///
+    /// `Ssd1331` doesn't turn the display off automatically when dropped. Sending commands can
+    /// fail, and `Drop` has no way to report the error, and it would also need a delay for the
+    /// fade. Dropping the driver leaves the panel showing whatever it last displayed; call this
+    /// method instead to leave it in a known state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let (spi, dc) = display.shutdown(&mut delay);
+    /// ```
+    pub fn shutdown<DELAY>(mut self, delay: &mut DELAY) -> (SPI, DC)
+    where
+        DELAY: DelayMs<u8>,
+    {
+        Command::DeactivateScroll
+            .send(&mut self.spi, &mut self.dc)
+            .ok();
 
-        match rot {
-            DisplayRotation::Rotate0 => {
-                Command::RemapAndColorDepth(
-                    false,
-                    false,
-                    ColorMode::CM65k,
-                    AddressIncrementMode::Horizontal,
-                )
-                .send(&mut self.spi, &mut self.dc)?;
-            }
-            DisplayRotation::Rotate90 => {
-                Command::RemapAndColorDepth(
-                    true,
-                    false,
-                    ColorMode::CM65k,
-                    AddressIncrementMode::Vertical,
-                )
-                .send(&mut self.spi, &mut self.dc)?;
-            }
-            DisplayRotation::Rotate180 => {
-                Command::RemapAndColorDepth(
-                    true,
-                    true,
-                    ColorMode::CM65k,
-                    AddressIncrementMode::Horizontal,
-                )
-                .send(&mut self.spi, &mut self.dc)?;
-            }
-            DisplayRotation::Rotate270 => {
-                Command::RemapAndColorDepth(
-                    false,
-                    true,
-                    ColorMode::CM65k,
-                    AddressIncrementMode::Vertical,
-                )
-                .send(&mut self.spi, &mut self.dc)?;
-            }
-        };
+        if self.turn_off_faded(delay, SHUTDOWN_FADE_STEPS).is_err() {
+            self.turn_off().ok();
+        }
 
-        Ok(())
+        (self.spi, self.dc)
     }
 
-    /// Get the current rotation of the display
-    pub fn rotation(&self) -> DisplayRotation {
-        self.display_rotation
+    /// Get mutable access to the raw framebuffer
+    ///
+    /// Each pixel is two bytes of RGB565, stored big endian (high byte first) by default. The
+    /// byte order can be changed with [`init_with`](#method.init_with), which can also select 256
+    /// colour mode where each pixel is a single RGB332 byte and the buffer is half the size.
+    /// Pixels are stored row by row in the order they are sent
+    /// to the display: 96 pixels per row with `Rotate0` and `Rotate180`, or 64 pixels per row with
+    /// `Rotate90` and `Rotate270`. With [software rotation](#method.set_software_rotation) the
+    /// buffer always uses the unrotated 96 pixel wide layout.
+    ///
+    /// This allows image data in the matching format to be copied straight into the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Full screen 96x64 image data
+    /// let image = [0u8; 96 * 64 * 2];
+    ///
+    /// display.buffer_mut().copy_from_slice(&image);
+    /// display.flush().unwrap();
+    /// ```
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        let len = self.frame_len();
+
+        &mut self.buffer.bytes_mut()[0..len]
     }
 
-    /// Turn the display on (eg exiting sleep mode)
-    pub fn turn_on(&mut self) -> Result<(), Error<CommE, PinE>> {
-        Command::DisplayOn(true).send(&mut self.spi, &mut self.dc)
+    /// Clear the display buffer
+    ///
+    /// `display.flush()` must be called to update the display
+    pub fn clear(&mut self) {
+        self.buffer.bytes_mut().fill(0);
+        self.mark_all_dirty();
     }
 
-    /// Turn the display off (enter sleep mode)
-    pub fn turn_off(&mut self) -> Result<(), Error<CommE, PinE>> {
-        Command::DisplayOn(false).send(&mut self.spi, &mut self.dc)
+    /// Fill the display buffer with an RGB565 colour
+    ///
+    /// Every pixel is set to `color` in the framebuffer's current format, so the next
+    /// [`flush`](#method.flush) shows a uniform field. With the `graphics` feature,
+    /// `embedded-graphics`' [`DrawTarget::clear`] does the same with an `Rgb565` colour.
+    ///
+    /// `display.flush()` must be called to update the display.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Dark blue background
+    /// display.clear_color(0x0010);
+    /// display.flush().unwrap();
+    /// ```
+    ///
+    /// [`DrawTarget::clear`]: https://docs.rs/embedded-graphics-core/latest/embedded_graphics_core/draw_target/trait.DrawTarget.html#method.clear
+    pub fn clear_color(&mut self, color: u16) {
+        if self.dithers() {
+            for idx in 0..self.frame_len() {
+                self.write_pixel(idx, color);
+            }
+
+            self.mark_all_dirty();
+
+            return;
+        }
+
+        let bytes = self.encode_pixel(color);
+        let bpp = self.bytes_per_pixel();
+
+        for pixel in self.buffer.bytes_mut().chunks_exact_mut(bpp) {
+            pixel.copy_from_slice(&bytes[0..bpp]);
+        }
+
+        self.mark_all_dirty();
     }
-}
 
-#[cfg(feature = "graphics")]
-use embedded_graphics_core::{
-    draw_target::DrawTarget,
-    geometry::Size,
-    geometry::{Dimensions, OriginDimensions},
-    pixelcolor::{
-        raw::{RawData, RawU16},
-        Rgb565,
-    },
-    Pixel,
-};
+    /// Fill the part of the framebuffer the panel shows with `color`, leaving the rest untouched
+    ///
+    /// With the default multiplex ratio the controller drives all 64 of its rows, so this fills
+    /// the whole display like [`clear`](#method.clear) does, but with any RGB565 colour. On panels
+    /// driven with a smaller multiplex ratio, only the first `multiplex + 1` GDDRAM rows (see
+    /// [`Config::multiplex`](./struct.Config.html#structfield.multiplex)) are shown, and the
+    /// framebuffer bytes for the other rows are left as they are. This takes the rotation into
+    /// account, so with 90 or 270 degree rotation the active rows are logical columns.
+    ///
+    /// Only the framebuffer is changed, so [`flush`](#method.flush) must be called to update the
+    /// display.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Dark blue background
+    /// display.clear_active(0x0010);
+    /// display.flush().unwrap();
+    /// ```
+    pub fn clear_active(&mut self, color: u16) {
+        let (w, h) = self.dimensions();
+        let last_row = self.config.multiplex;
 
-#[cfg(feature = "graphics")]
-impl<SPI, DC> DrawTarget for Ssd1331<SPI, DC>
-where
-    SPI: hal::blocking::spi::Write<u8>,
-    DC: OutputPin,
-{
-    type Color = Rgb565;
-    type Error = core::convert::Infallible;
+        for y in 0..h {
+            for x in 0..w {
+                if self.to_address((x, y)).1 > last_row {
+                    continue;
+                }
 
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+                if let Some(idx) = self.pixel_index(u32::from(x), u32::from(y)) {
+                    self.write_pixel(idx, color);
+                }
+            }
+        }
+
+        self.mark_all_dirty();
+    }
+
+    /// Reset the display
+    ///
+    /// This method brings the RST pin high for 1ms, then low for 1ms to reset the module, then
+    /// high again. This is [`reset_with_timing`](#method.reset_with_timing) with a 1ms pulse and
+    /// settle time.
+    ///
+    /// This assumes `rst` is only connected to this display. If several displays share a reset
+    /// line, use [`reset_shared`](#method.reset_shared) once for all of them instead.
+    pub fn reset<RST, DELAY>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<CommE, PinE>>
     where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
+        RST: OutputPin<Error = PinE>,
+        DELAY: DelayMs<u8>,
     {
-        let bb = self.bounding_box();
+        self.reset_with_timing(rst, delay, 1, 1)
+    }
 
-        pixels
-            .into_iter()
-            .filter(|Pixel(pos, _color)| bb.contains(*pos))
-            .for_each(|Pixel(pos, color)| {
-                self.set_pixel(pos.x as u32, pos.y as u32, RawU16::from(color).into_inner())
-            });
+    /// Reset the display with a custom reset pulse length and settle time
+    ///
+    /// The RST pin is brought high and held there for `settle_ms` milliseconds so it starts from
+    /// a settled inactive level, then brought low for `low_ms` milliseconds to reset the module,
+    /// then high again. Some modules don't reset reliably with the 1ms pulse used by
+    /// [`reset`](#method.reset), and stay blank after power on; a longer pulse, like 10ms, fixes
+    /// this.
+    ///
+    /// Like `reset`, this assumes `rst` is only connected to this display.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pins and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut rst = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// // Hold reset low for 10ms for modules that need a longer pulse
+    /// display.reset_with_timing(&mut rst, &mut delay, 10, 1).unwrap();
+    /// display.init().unwrap();
+    /// ```
+    pub fn reset_with_timing<RST, DELAY>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+        low_ms: u8,
+        settle_ms: u8,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        RST: OutputPin<Error = PinE>,
+        DELAY: DelayMs<u8>,
+    {
+        self.window_full = false;
+
+        rst.set_high().map_err(Error::Pin)?;
+        delay.delay_ms(settle_ms);
+        rst.set_low().map_err(Error::Pin)?;
+        delay.delay_ms(low_ms);
+        rst.set_high().map_err(Error::Pin)?;
 
         Ok(())
     }
-}
-
-#[cfg(feature = "graphics")]
-impl<SPI, DC> OriginDimensions for Ssd1331<SPI, DC>
-where
-    SPI: hal::blocking::spi::Write<u8>,
-    DC: OutputPin,
-{
-    fn size(&self) -> Size {
-        let (w, h) = self.dimensions();
 
-        Size::new(w.into(), h.into())
+    /// Reset and reinitialise the display, then restore its contents from the framebuffer
+    ///
+    /// This is intended as a recovery path after the panel has lost its state, for example from a
+    /// brown out, while the firmware kept running. The display is [`reset`](#method.reset) and
+    /// initialised as in [`init`](#method.init) using the current configuration. The framebuffer is
+    /// left untouched and finishes with a [`flush`](#method.flush), so the previous contents are
+    /// visible again once this method returns. The flush is sent before the display is turned back
+    /// on, so no noise is shown in between.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pins and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut rst = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xffff);
+    /// display.flush().unwrap();
+    ///
+    /// // ...the panel browns out...
+    ///
+    /// display.reinit(&mut rst, &mut delay).unwrap();
+    /// ```
+    pub fn reinit<RST, DELAY>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        RST: OutputPin<Error = PinE>,
+        DELAY: DelayMs<u8>,
+    {
+        self.reset(rst, delay)?;
+        self.init_off()?;
+        self.flush()?;
+
+        self.turn_on()
+    }
+
+    /// Send the full framebuffer to the display
+    ///
+    /// This resets the draw area the full size of the display
+    ///
+    /// The buffer is sent in the order it is stored, which always matches the address increment
+    /// mode the controller is in. With `Rotate90` and `Rotate270` the controller fills its memory
+    /// column by column, and each 64 pixel buffer row is exactly one controller column.
+    ///
+    /// Before anything is sent, the framebuffer storage is checked to be large enough for a full
+    /// frame (12,288 bytes in 65k colour mode). A wrongly sized borrowed buffer would otherwise
+    /// send a scrambled frame, so this panics in debug builds and returns an
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error in release builds. The
+    /// check is a single length comparison, and always passes for the default owned buffer. Every
+    /// method that sends the framebuffer makes the same check, including
+    /// [`flush_dirty`](#method.flush_dirty), [`flush_region`](#method.flush_region),
+    /// [`flush_rows`](#method.flush_rows) and [`flush_yielding`](#method.flush_yielding).
+    pub fn flush(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.check_buffer_len()?;
+
+        if self.fast_flush && self.window_full {
+            self.send_frame()
+        } else {
+            self.flush_full()
+        }
+    }
+
+    /// Send the full framebuffer to the display, always resetting the draw area first
+    ///
+    /// This behaves like [`flush`](#method.flush) with fast flush mode disabled, regardless of
+    /// whether it is enabled. Use it to recover if the controller's draw area may have been changed
+    /// without the driver knowing.
+    pub fn flush_full(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.check_buffer_len()?;
+
+        // Ensure the display buffer is at the origin of the display before we send the full frame
+        // to prevent accidental offsets
+        self.set_window(DrawArea::full())?;
+
+        self.send_frame()
+    }
+
+    /// Record the transfers a full frame [`flush`](#method.flush) would make into `out`, without
+    /// sending anything
+    ///
+    /// This allows the frame to be submitted through a DMA or other transaction mechanism instead
+    /// of this driver's SPI interface. The returned number of [`Op`](./enum.Op.html)s at the
+    /// start of `out` must be sent in order. A full frame is always two ops: the draw area
+    /// commands, then the framebuffer as pixel data. The pixel data borrows the framebuffer, so
+    /// the display can't be drawn to until the ops are dropped.
+    ///
+    /// Pixel data is the framebuffer as stored. The controller expects big endian pixels, so with
+    /// a [`ByteOrder::LittleEndian`](./enum.ByteOrder.html#variant.LittleEndian) framebuffer each
+    /// pair of bytes must be swapped as it is sent.
+    ///
+    /// The driver doesn't know when or whether the ops are sent, so unlike `flush` this doesn't
+    /// count a frame or clear the [dirty region](#method.flush_dirty).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than two ops. Like `flush`, this also checks the framebuffer
+    /// storage holds a full frame, panicking in debug builds. In release builds nothing is
+    /// recorded and zero is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Op, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xffff);
+    ///
+    /// let mut ops = [Op::Command(&[]); 2];
+    /// let len = display.record_flush(&mut ops);
+    ///
+    /// for op in &ops[0..len] {
+    ///     // Set the DC pin low for commands and high for data, then start a DMA transfer of
+    ///     // `op.bytes()`
+    /// #   let _ = (op.is_command(), op.bytes());
+    /// }
+    ///
+    /// assert_eq!(ops[1].bytes().len(), 96 * 64 * 2);
+    /// ```
+    pub fn record_flush<'a>(&'a self, out: &mut [Op<'a>]) -> usize {
+        if self.check_buffer_len().is_err() {
+            return 0;
+        }
+
+        let ops = [
+            Op::Command(&FULL_DRAW_AREA),
+            Op::Data(&self.buffer.bytes()[0..self.frame_len()]),
+        ];
+
+        out[0..ops.len()].copy_from_slice(&ops);
+
+        ops.len()
+    }
+
+    /// Fill the whole display with one colour without touching the framebuffer
+    ///
+    /// A repeating two byte RGB565 pattern is streamed to the display in small chunks, avoiding
+    /// clearing and sending the 12,288 byte framebuffer. This is useful for panel diagnostics and
+    /// for measuring raw SPI throughput.
+    ///
+    /// The framebuffer is not updated, so the next [`flush`](#method.flush) restores whatever it
+    /// contained before.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Flash the display white, then restore the framebuffer contents
+    /// display.flush_solid(0xffff).unwrap();
+    /// display.flush().unwrap();
+    /// ```
+    pub fn flush_solid(&mut self, color: u16) -> Result<(), Error<CommE, PinE>> {
+        self.set_window(DrawArea::full())?;
+
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        let mut chunk = [0u8; 64];
+        let bpp = self.bytes_per_pixel();
+
+        // The controller always expects big endian pixel data
+        let bytes = match self.config.color_mode {
+            ColorMode::CM256 => [rgb565_to_rgb332(color), 0],
+            ColorMode::CM65k => color.to_be_bytes(),
+        };
+
+        for pixel in chunk.chunks_exact_mut(bpp) {
+            pixel.copy_from_slice(&bytes[0..bpp]);
+        }
+
+        self.window_full = false;
+
+        for _ in 0..self.frame_len() / chunk.len() {
+            write_chunked(&mut self.spi, &chunk, self.max_transfer_size)?;
+        }
+
+        self.window_full = true;
+
+        Ok(())
+    }
+
+    /// Enable or disable fast flush mode
+    ///
+    /// A full frame [`flush`](#method.flush) normally sets the draw area to the whole display
+    /// before sending the framebuffer. After a full frame is sent the controller's address pointer
+    /// has wrapped back to the start of the display, so with fast flush mode enabled the driver
+    /// skips setting the draw area again while it knows the area is unchanged. This saves 6
+    /// command bytes and two data/command switches per frame. At 12,288 bytes per frame this is
+    /// a small saving, mostly noticeable on slow or high latency buses.
+    ///
+    /// The driver stops skipping the draw area after anything it sends that moves the window or
+    /// address pointer, such as [`write_data`](#method.write_data), a partial flush, a rotation
+    /// change or a [`reset`](#method.reset). This relies on the driver being the only thing that
+    /// talks to the controller. If commands may have been sent some other way, or the panel may
+    /// have reset without the driver knowing, call [`flush_full`](#method.flush_full) instead.
+    ///
+    /// Fast flush mode is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.set_fast_flush(true);
+    ///
+    /// // The first flush sets the draw area, later ones only send pixel data
+    /// display.flush().unwrap();
+    /// display.flush().unwrap();
+    /// ```
+    pub fn set_fast_flush(&mut self, enabled: bool) {
+        self.fast_flush = enabled;
+    }
+
+    /// Set the largest number of bytes sent in a single SPI write when sending the framebuffer
+    ///
+    /// By default a full frame is sent in one 12,288 byte write. Some HALs limit the length of a
+    /// single transfer, for example to what fits in a DMA length register or FIFO, and reject or
+    /// truncate longer ones, which can leave the bottom of the display garbled. With a limit set,
+    /// [`flush`](#method.flush) and the other flush methods, [`flush_solid`](#method.flush_solid)
+    /// and [`draw_image_raw`](#method.draw_image_raw) split pixel data into writes of at most
+    /// `size` bytes, holding the DC pin high between them so the controller sees one continuous
+    /// stream of pixel data.
+    ///
+    /// Sizes below 2 are treated as 2, one pixel in 65k colour mode. Pass `usize::MAX` to go back
+    /// to unlimited writes. Data passed to [`write_data`](#method.write_data) is sent as given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // This HAL's DMA can only send 4KiB at a time
+    /// display.set_max_transfer_size(4096);
+    /// display.flush().unwrap();
+    /// ```
+    pub fn set_max_transfer_size(&mut self, size: usize) {
+        self.max_transfer_size = size.max(2);
+    }
+
+    /// Check the framebuffer storage holds a full frame in the current colour mode
+    fn check_buffer_len(&self) -> Result<(), Error<CommE, PinE>> {
+        let len = self.buffer.bytes().len();
+
+        debug_assert!(
+            len >= self.frame_len(),
+            "framebuffer is {} bytes, a full frame needs {}",
+            len,
+            self.frame_len()
+        );
+
+        if len < self.frame_len() {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(())
+    }
+
+    /// Send the full framebuffer into the current draw area, which must be the full display
+    fn send_frame(&mut self) -> Result<(), Error<CommE, PinE>> {
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        // The address pointer is only back at the start once the whole frame is sent
+        self.window_full = false;
+        self.send_buffer(0..self.frame_len())?;
+        self.window_full = true;
+
+        self.frame_count = self.frame_count.wrapping_add(1);
+        self.dirty = None;
+
+        Ok(())
+    }
+
+    /// Send the full framebuffer to the display, calling `yield_now` between chunks
+    ///
+    /// The framebuffer is sent in chunks of 1024 bytes, with `yield_now` called between each one.
+    /// This lets a cooperative scheduler run other tasks during the transfer instead of blocking
+    /// for the whole frame. The bytes sent are identical to [`flush`](#method.flush); only the
+    /// scheduling between them changes, and the framebuffer storage is checked the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let mut yields = 0;
+    ///
+    /// display.flush_yielding(|| yields += 1).unwrap();
+    ///
+    /// assert_eq!(yields, 11);
+    /// ```
+    pub fn flush_yielding(
+        &mut self,
+        mut yield_now: impl FnMut(),
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.check_buffer_len()?;
+
+        self.set_window(DrawArea::full())?;
+
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        self.window_full = false;
+
+        let len = self.frame_len();
+
+        for start in (0..len).step_by(YIELD_CHUNK_SIZE) {
+            if start > 0 {
+                yield_now();
+            }
+
+            self.send_buffer(start..len.min(start + YIELD_CHUNK_SIZE))?;
+        }
+
+        self.window_full = true;
+
+        self.frame_count = self.frame_count.wrapping_add(1);
+        self.dirty = None;
+
+        Ok(())
+    }
+
+    /// Send only the part of the framebuffer that changed since it was last sent
+    ///
+    /// The driver tracks the smallest rectangle containing every pixel changed through its drawing
+    /// methods. This sends that rectangle, which is quicker than a full [`flush`](#method.flush)
+    /// when only a small part of the display changes each frame. Nothing is sent if nothing has
+    /// changed. Any full frame flush also clears the changed region.
+    ///
+    /// The region is only cleared once it has been sent successfully. If sending fails, it is
+    /// kept, so the next call sends the changes again instead of losing them.
+    ///
+    /// Everything is considered changed when the display is created, cleared or rotated. Changes
+    /// made directly through [`buffer_mut`](#method.buffer_mut) can't be tracked, so call
+    /// [`mark_all_dirty`](#method.mark_all_dirty) after them.
+    ///
+    /// This leaves the draw area set to the flushed region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Only the 3x2 pixel region containing both pixels is sent
+    /// display.set_pixel(10, 20, 0xffff);
+    /// display.set_pixel(12, 21, 0xffff);
+    /// display.flush_dirty().unwrap();
+    /// ```
+    pub fn flush_dirty(&mut self) -> Result<(), Error<CommE, PinE>> {
+        if let Some((top_left, bottom_right)) = self.dirty {
+            self.flush_area(top_left, bottom_right)?;
+
+            self.dirty = None;
+        }
+
+        Ok(())
+    }
+
+    /// Mark the whole framebuffer as changed, so the next [`flush_dirty`](#method.flush_dirty)
+    /// sends all of it
+    ///
+    /// This is needed after changing the framebuffer directly through
+    /// [`buffer_mut`](#method.buffer_mut), as the driver can't see those changes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// display.buffer_mut().copy_from_slice(&[0xff; 96 * 64 * 2]);
+    /// display.mark_all_dirty();
+    /// display.flush_dirty().unwrap();
+    /// ```
+    pub fn mark_all_dirty(&mut self) {
+        let (w, h) = self.dimensions();
+
+        self.dirty = Some(((0, 0), (w - 1, h - 1)));
+    }
+
+    /// Check whether the framebuffer has changed since it was last sent
+    ///
+    /// This returns `true` when [`flush_dirty`](#method.flush_dirty) would send something. Event
+    /// loops can use it to skip rendering and flushing entirely while nothing changes, saving
+    /// power.
+    ///
+    /// Changes made directly through [`buffer_mut`](#method.buffer_mut) aren't seen by the
+    /// driver, so they don't set this. Call [`mark_all_dirty`](#method.mark_all_dirty) after them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// assert!(!display.needs_flush());
+    ///
+    /// display.set_pixel(10, 20, 0xffff);
+    ///
+    /// assert!(display.needs_flush());
+    ///
+    /// display.flush_dirty().unwrap();
+    ///
+    /// assert!(!display.needs_flush());
+    /// ```
+    pub fn needs_flush(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Get the region changed since it was last sent, or `None` if nothing has changed
+    ///
+    /// This is the region [`flush_dirty`](#method.flush_dirty) would send. It is in the same
+    /// logical coordinate space used for drawing, with the origin at the top left of the display
+    /// as currently rotated. With `Rotate90` or `Rotate270` it lies within 64 x 96 pixels, not the
+    /// controller's physical 96 x 64 columns and rows.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use ssd1331::{DisplayRotation::Rotate90, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate90);
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// assert_eq!(display.dirty_rectangle(), None);
+    ///
+    /// display.set_pixel(10, 80, 0xffff);
+    /// display.set_pixel(12, 81, 0xffff);
+    ///
+    /// assert_eq!(
+    ///     display.dirty_rectangle(),
+    ///     Some(Rectangle::new(Point::new(10, 80), Size::new(3, 2)))
+    /// );
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn dirty_rectangle(&self) -> Option<Rectangle> {
+        self.dirty.map(|(top_left, bottom_right)| {
+            Rectangle::with_corners(
+                Point::new(top_left.0.into(), top_left.1.into()),
+                Point::new(bottom_right.0.into(), bottom_right.1.into()),
+            )
+        })
+    }
+
+    /// Number of full frames sent to the display
+    ///
+    /// This counts each successful [`flush`](#method.flush), [`flush_full`](#method.flush_full)
+    /// and [`flush_yielding`](#method.flush_yielding) since the display was created or
+    /// [`reset_frame_count`](#method.reset_frame_count) was last called. Combined with a timer this
+    /// gives a frames per second readout. The count wraps back to zero after `u32::MAX` frames.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// for _ in 0..10 {
+    ///     display.flush().unwrap();
+    /// }
+    ///
+    /// assert_eq!(display.frame_count(), 10);
+    ///
+    /// display.reset_frame_count();
+    ///
+    /// assert_eq!(display.frame_count(), 0);
+    /// ```
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Reset the [frame count](#method.frame_count) to zero
+    pub fn reset_frame_count(&mut self) {
+        self.frame_count = 0;
+    }
+
+    /// Set the top left and bottom right corners of a bounding box to draw to
+    ///
+    /// Corners are inclusive, in the controller's (column, row) address space of 96 columns by 64
+    /// rows. Coordinates past the edge of this space are handled according to the
+    /// [bounds policy](#method.set_bounds_policy): by default an
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and
+    /// nothing is sent. A `start` corner below or to the right of `end` is always an error.
+    ///
+    /// The corners are checked by building a [`DrawArea`](./struct.DrawArea.html). To check a
+    /// window once and reuse it, build the `DrawArea` directly and pass it to
+    /// [`set_window`](#method.set_window).
+    pub fn set_draw_area(
+        &mut self,
+        start: (u8, u8),
+        end: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let area = self.checked_area(start, end)?;
+
+        self.set_window(area)
+    }
+
+    /// Build a `DrawArea` from two corners, applying the bounds policy
+    fn checked_area(&self, start: (u8, u8), end: (u8, u8)) -> Result<DrawArea, Error<CommE, PinE>> {
+        let (max_col, max_row) = (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1);
+
+        let (start, end) = match self.bounds_policy {
+            BoundsPolicy::Clamp => (
+                (start.0.min(max_col), start.1.min(max_row)),
+                (end.0.min(max_col), end.1.min(max_row)),
+            ),
+            // Checked when the `DrawArea` is created
+            BoundsPolicy::Error => (start, end),
+        };
+
+        DrawArea::new(start, end).map_err(|_| Error::InvalidArgument)
+    }
+
+    /// Set the draw area to a validated [`DrawArea`](./struct.DrawArea.html)
+    ///
+    /// This behaves like [`set_draw_area`](#method.set_draw_area), but as a `DrawArea` is always
+    /// valid, the bounds policy doesn't apply and the only errors are from sending the commands.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, DrawArea, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let area = DrawArea::new((10, 10), (11, 11)).unwrap();
+    ///
+    /// display.set_window(area).unwrap();
+    /// display.write_data(&[0xff; 2 * 2 * 2]).unwrap();
+    /// ```
+    pub fn set_window(&mut self, area: DrawArea) -> Result<(), Error<CommE, PinE>> {
+        let (start, end) = (area.top_left(), area.bottom_right());
+
+        let (column, column_len) = Command::ColumnAddress(start.0, end.0).encode();
+        let (row, row_len) = Command::RowAddress(start.1, end.1).encode();
+
+        // Both address commands are sent in one write to save per transfer overhead
+        let mut commands = [0; 6];
+        let len = column_len + row_len;
+
+        commands[0..column_len].copy_from_slice(&column[0..column_len]);
+        commands[column_len..len].copy_from_slice(&row[0..row_len]);
+
+        self.window_full = false;
+
+        // 1 = data, 0 = command
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.spi.write(&commands[0..len]).map_err(Error::Comm)?;
+
+        self.window_full = area == DrawArea::full();
+
+        Ok(())
+    }
+
+    /// Send raw pixel data to the current draw area
+    ///
+    /// `data` is written directly to the display, bypassing the framebuffer. Each pixel is two
+    /// bytes of big endian RGB565. The controller fills the draw area set by
+    /// [`set_draw_area`](#method.set_draw_area), wrapping back to its start when the end is
+    /// reached.
+    pub fn write_data(&mut self, data: &[u8]) -> Result<(), Error<CommE, PinE>> {
+        self.window_full = false;
+
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        self.spi.write(data).map_err(Error::Comm)
+    }
+
+    /// Start writing data at `(x, y)` in the controller's (column, row) address space
+    ///
+    /// This sets a draw area from `(x, y)` to the bottom right corner of the display, so it's a
+    /// lighter way to position the write pointer than working out a full
+    /// [`set_draw_area`](#method.set_draw_area). Pixels sent afterwards with
+    /// [`write_data`](#method.write_data) advance automatically according to the address increment
+    /// mode of the current rotation:
+    ///
+    /// * `Rotate0` and `Rotate180` increment horizontally: each pixel moves one column right. After
+    ///   column 95 the pointer wraps back to column `x` on the next row.
+    /// * `Rotate90` and `Rotate270` increment vertically: each pixel moves one row down. After row
+    ///   63 the pointer wraps back to row `y` in the next column.
+    ///
+    /// After the bottom right corner the pointer wraps back to `(x, y)`. Coordinates past the edge
+    /// of the display are handled according to the [bounds policy](#method.set_bounds_policy).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Stream three white pixels starting at column 10, row 20
+    /// display.set_cursor(10, 20).unwrap();
+    /// display.write_data(&[0xff; 3 * 2]).unwrap();
+    /// ```
+    pub fn set_cursor(&mut self, x: u8, y: u8) -> Result<(), Error<CommE, PinE>> {
+        self.set_draw_area((x, y), (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1))
+    }
+
+    /// Choose how out of range coordinates passed to [`set_draw_area`](#method.set_draw_area) and
+    /// [`set_cursor`](#method.set_cursor) are handled
+    ///
+    /// * [`BoundsPolicy::Error`](./enum.BoundsPolicy.html#variant.Error) (the default) returns an
+    ///   [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error without sending
+    ///   anything to the display.
+    /// * [`BoundsPolicy::Clamp`](./enum.BoundsPolicy.html#variant.Clamp) moves coordinates past the
+    ///   edge of the display to the edge, so the window covers only the on screen part of the
+    ///   requested area.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{BoundsPolicy, DisplayRotation::Rotate0, Error, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// assert!(matches!(
+    ///     display.set_draw_area((90, 60), (100, 70)),
+    ///     Err(Error::InvalidArgument)
+    /// ));
+    ///
+    /// display.set_bounds_policy(BoundsPolicy::Clamp);
+    ///
+    /// // Clamped to (90, 60), (95, 63)
+    /// assert!(display.set_draw_area((90, 60), (100, 70)).is_ok());
+    /// ```
+    pub fn set_bounds_policy(&mut self, policy: BoundsPolicy) {
+        self.bounds_policy = policy;
+    }
+
+    /// Set the draw area, run `f`, then reset the draw area to the full display
+    ///
+    /// Data written inside `f` (for example with [`write_data`](#method.write_data)) lands in the
+    /// given window. Once `f` returns, the draw area is always reset to cover the whole display,
+    /// whatever `f` returned, so later writes aren't offset by a window left behind. The value
+    /// returned by `f` is passed back to the caller.
+    ///
+    /// To pass the corners as tuples instead, use [`with_draw_area`](#method.with_draw_area).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, DrawArea, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Fill a 2x2 pixel square at (10, 10) with white
+    /// let area = DrawArea::new((10, 10), (11, 11)).unwrap();
+    ///
+    /// display
+    ///     .with_window(area, |display| display.write_data(&[0xff; 2 * 2 * 2]))
+    ///     .unwrap()
+    ///     .unwrap();
+    /// ```
+    pub fn with_window<R>(
+        &mut self,
+        area: DrawArea,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<CommE, PinE>> {
+        self.set_window(area)?;
+
+        let result = f(self);
+
+        self.set_window(DrawArea::full())?;
+
+        Ok(result)
+    }
+
+    /// Run a closure with the draw area set to the given corners, then reset it to the full display
+    ///
+    /// This builds a [`DrawArea`](./struct.DrawArea.html) from the corners the same way as
+    /// [`set_draw_area`](#method.set_draw_area), including the
+    /// [bounds policy](#method.set_bounds_policy), then behaves like
+    /// [`with_window`](#method.with_window). If the corners are rejected, `f` isn't called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Fill a 2x2 pixel square at (10, 10) with white
+    /// display
+    ///     .with_draw_area((10, 10), (11, 11), |display| {
+    ///         display.write_data(&[0xff; 2 * 2 * 2])
+    ///     })
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// // Swapped corners are rejected
+    /// assert!(display
+    ///     .with_draw_area((11, 11), (10, 10), |_| ())
+    ///     .is_err());
+    /// ```
+    pub fn with_draw_area<R>(
+        &mut self,
+        start: (u8, u8),
+        end: (u8, u8),
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<CommE, PinE>> {
+        let area = self.checked_area(start, end)?;
+
+        self.with_window(area, f)
+    }
+
+    /// Set the value for an individual pixel.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: u16) {
+        if let Some(idx) = self.pixel_index(x, y) {
+            self.write_pixel(idx, value);
+            self.mark_dirty((x as u8, y as u8), (x as u8, y as u8));
+        }
+    }
+
+    /// Get the RGB565 value of an individual pixel in the framebuffer, or `None` if it is off
+    /// screen
+    ///
+    /// This reads back what was drawn, using the same rotation aware indexing as
+    /// [`set_pixel`](#method.set_pixel), so it can be used for things like hit testing without
+    /// keeping a separate copy of the display. The value is converted from the framebuffer's
+    /// [`ByteOrder`](./enum.ByteOrder.html). In 256 colour mode pixels are stored with fewer bits,
+    /// so the value read back is the nearest RGB565 colour to the stored RGB332 one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate90, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate90);
+    ///
+    /// display.set_pixel(10, 80, 0xf800);
+    ///
+    /// assert_eq!(display.get_pixel(10, 80), Some(0xf800));
+    /// assert_eq!(display.get_pixel(10, 96), None);
+    /// ```
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<u16> {
+        self.pixel_index(x, y).map(|idx| self.read_pixel(idx))
+    }
+
+    /// Set the value of the pixel at a byte offset into the framebuffer
+    ///
+    /// This skips the coordinate and rotation handling done by [`set_pixel`](#method.set_pixel),
+    /// which can be measurable in tight rendering loops. `idx` must be an offset returned by
+    /// [`pixel_index`](#method.pixel_index) for the current rotation and colour mode. Other
+    /// offsets won't cause undefined behaviour, but may write half of one pixel and half of the
+    /// next, or panic if past the end of the framebuffer.
+    ///
+    /// The exact pixel written isn't known, so the whole display is marked as dirty for
+    /// [`flush_dirty`](#method.flush_dirty).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// // Draw a red diagonal line, computing each offset once up front
+    /// let offsets: Vec<usize> = (0..64).filter_map(|i| display.pixel_index(i, i)).collect();
+    ///
+    /// for idx in offsets {
+    ///     display.set_pixel_unchecked(idx, 0xf800);
+    /// }
+    /// ```
+    pub fn set_pixel_unchecked(&mut self, idx: usize, value: u16) {
+        self.write_pixel(idx, value);
+        self.mark_all_dirty();
+    }
+
+    /// Set the colour of the pixel at `point`
+    ///
+    /// This is a convenience wrapper around [`set_pixel`](#method.set_pixel) for use with
+    /// `embedded-graphics` types. Points with negative coordinates, or coordinates past the edge of
+    /// the display, are off screen and are ignored.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display.set_pixel_at(Point::new(10, 20), Rgb565::RED);
+    ///
+    /// // Off screen, so nothing is drawn
+    /// display.set_pixel_at(Point::new(-1, 20), Rgb565::RED);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn set_pixel_at(&mut self, point: Point, color: Rgb565) {
+        if point.x >= 0 && point.y >= 0 {
+            self.set_pixel(
+                point.x as u32,
+                point.y as u32,
+                RawU16::from(color).into_inner(),
+            );
+        }
+    }
+
+    /// Draw pixels into the framebuffer and flush only the region that contains them
+    ///
+    /// This is useful for sparse updates like scatter plots, where sending the whole frame for a
+    /// few changed pixels is wasteful. The smallest rectangle containing every on screen pixel is
+    /// sent to the display. Off screen pixels are ignored, and if there are no on screen pixels
+    /// (including when `pixels` is empty) nothing is sent.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let samples = [3, 7, 12, 9, 4];
+    ///
+    /// // Only the 5x10 pixel region containing the points is sent
+    /// display
+    ///     .draw_and_flush(samples.iter().enumerate().map(|(x, y)| {
+    ///         Pixel(Point::new(x as i32 + 20, *y as i32 + 10), Rgb565::GREEN)
+    ///     }))
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn draw_and_flush<I>(&mut self, pixels: I) -> Result<(), Error<CommE, PinE>>
+    where
+        I: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let (w, h) = self.dimensions();
+        let mut bounds: Option<((u8, u8), (u8, u8))> = None;
+
+        for Pixel(point, color) in pixels {
+            if point.x < 0 || point.y < 0 || point.x >= i32::from(w) || point.y >= i32::from(h) {
+                continue;
+            }
+
+            let (x, y) = (point.x as u8, point.y as u8);
+
+            self.set_pixel(u32::from(x), u32::from(y), RawU16::from(color).into_inner());
+
+            bounds = Some(match bounds {
+                Some(((x0, y0), (x1, y1))) => ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))),
+                None => ((x, y), (x, y)),
+            });
+        }
+
+        match bounds {
+            Some((top_left, bottom_right)) => self.flush_area(top_left, bottom_right),
+            None => Ok(()),
+        }
+    }
+
+    /// Draw an `embedded-graphics` object into the framebuffer and send the full frame to the
+    /// display
+    ///
+    /// This is the same as calling `drawable.draw(&mut display)` followed by
+    /// [`flush`](#method.flush), so the whole framebuffer is sent, not just the area covered by
+    /// the object. To send only what has changed, draw the object and call
+    /// [`flush_dirty`](#method.flush_dirty) instead.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use embedded_graphics::{
+    ///     pixelcolor::Rgb565,
+    ///     prelude::*,
+    ///     primitives::{Circle, PrimitiveStyle},
+    /// };
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let circle =
+    ///     Circle::new(Point::new(32, 16), 32).into_styled(PrimitiveStyle::with_fill(Rgb565::RED));
+    ///
+    /// display.present(&circle).unwrap();
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn present<D>(&mut self, drawable: &D) -> Result<(), Error<CommE, PinE>>
+    where
+        D: Drawable<Color = Rgb565>,
+    {
+        // Drawing into the framebuffer can't fail
+        match drawable.draw(self) {
+            Ok(_) => {}
+            Err(e) => match e {},
+        }
+
+        self.flush()
+    }
+
+    /// Invert the colours of a rectangular region and send it to the display
+    ///
+    /// Each pixel between the inclusive `top_left` and `bottom_right` corners, in logical
+    /// (rotated) coordinates, is XORed with `0xffff` in the framebuffer, then only that region is
+    /// sent to the display. Calling this twice on the same region restores the original colours,
+    /// which makes it useful for flashing a selection highlight.
+    ///
+    /// Corners past the edge of the display are clamped to the edge. If the region is entirely off
+    /// screen nothing is changed or sent. An
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if
+    /// `top_left` is below or to the right of `bottom_right`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Highlight a menu item, then remove the highlight
+    /// display.invert_region((0, 10), (95, 19)).unwrap();
+    /// display.invert_region((0, 10), (95, 19)).unwrap();
+    /// ```
+    pub fn invert_region(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+
+        if top_left.0 > bottom_right.0 || top_left.1 > bottom_right.1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        if top_left.0 >= w || top_left.1 >= h {
+            return Ok(());
+        }
+
+        let bottom_right = (bottom_right.0.min(w - 1), bottom_right.1.min(h - 1));
+
+        for y in top_left.1..=bottom_right.1 {
+            for x in top_left.0..=bottom_right.0 {
+                if let Some(idx) = self.pixel_index(u32::from(x), u32::from(y)) {
+                    let bpp = self.bytes_per_pixel();
+
+                    for byte in self.buffer.bytes_mut()[idx..idx + bpp].iter_mut() {
+                        *byte ^= 0xff;
+                    }
+                }
+            }
+        }
+
+        self.mark_dirty(top_left, bottom_right);
+
+        self.flush_area(top_left, bottom_right)
+    }
+
+    /// Scale the brightness of a rectangular region of the framebuffer
+    ///
+    /// The display's contrast applies to the whole panel, so this brightens (or dims) part of it
+    /// in software instead, for example to make a notification stand out. The red, green and blue
+    /// channels of each RGB565 pixel between the inclusive `top_left` and `bottom_right` corners
+    /// are multiplied by `factor / 16` and clamped to their maximum, so 16 leaves pixels
+    /// unchanged, 24 brightens them by half and 8 halves them. Corners are in logical (rotated)
+    /// coordinates, and pixels outside the region are untouched.
+    ///
+    /// Only the framebuffer is changed, so the region must be sent with
+    /// [`flush`](#method.flush) or [`flush_dirty`](#method.flush_dirty) afterwards. The original
+    /// colours are overwritten, and clamped or rounded channels can't be recovered by scaling back
+    /// down; redraw the region to undo a highlight.
+    ///
+    /// Corners past the edge of the display are clamped to the edge. If the region is entirely off
+    /// screen nothing is changed. An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument)
+    /// error is returned if `top_left` is below or to the right of `bottom_right`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Make a notification banner twice as bright, then send it
+    /// display.highlight_region((0, 0), (95, 11), 32).unwrap();
+    /// display.flush_dirty().unwrap();
+    /// ```
+    pub fn highlight_region(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+        factor: u8,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+
+        if top_left.0 > bottom_right.0 || top_left.1 > bottom_right.1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        if top_left.0 >= w || top_left.1 >= h {
+            return Ok(());
+        }
+
+        let bottom_right = (bottom_right.0.min(w - 1), bottom_right.1.min(h - 1));
+        let scale = |channel: u16, max: u16| {
+            (u32::from(channel) * u32::from(factor) / 16).min(u32::from(max)) as u16
+        };
+
+        for y in top_left.1..=bottom_right.1 {
+            for x in top_left.0..=bottom_right.0 {
+                if let Some(idx) = self.pixel_index(u32::from(x), u32::from(y)) {
+                    let value = self.read_pixel(idx);
+
+                    let r = scale(value >> 11, 0x1f);
+                    let g = scale((value >> 5) & 0x3f, 0x3f);
+                    let b = scale(value & 0x1f, 0x1f);
+
+                    self.write_pixel(idx, (r << 11) | (g << 5) | b);
+                }
+            }
+        }
+
+        self.mark_dirty(top_left, bottom_right);
+
+        Ok(())
+    }
+
+    /// Shift the framebuffer contents by `dx` pixels right and `dy` pixels down
+    ///
+    /// Negative values shift left and up. Offsets are in logical (rotated) coordinates. Pixels
+    /// shifted past the edge of the display are lost, and the edges exposed by the shift are
+    /// filled with black. Shifts larger than the display clear it.
+    ///
+    /// Periodically moving static content by a pixel or two spreads wear across neighbouring
+    /// pixels, reducing OLED burn-in on always-on displays. See
+    /// [`antiburn_tick`](#method.antiburn_tick) for a ready made pattern.
+    ///
+    /// Only the framebuffer is updated, so [`flush`](#method.flush) must be called afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Move everything one pixel left and two down
+    /// display.shift_pixels(-1, 2);
+    /// display.flush().unwrap();
+    /// ```
+    pub fn shift_pixels(&mut self, dx: i8, dy: i8) {
+        let (w, h) = self.dimensions();
+        let (w, h) = (i32::from(w), i32::from(h));
+        let (dx, dy) = (i32::from(dx).clamp(-w, w), i32::from(dy).clamp(-h, h));
+
+        if dx == 0 && dy == 0 {
+            return;
+        }
+
+        let bpp = self.bytes_per_pixel();
+
+        // Visit destination pixels moving away from the direction of the shift, so each source
+        // pixel is read before it is overwritten
+        for j in 0..h {
+            let y = if dy > 0 { h - 1 - j } else { j };
+
+            for i in 0..w {
+                let x = if dx > 0 { w - 1 - i } else { i };
+
+                let dst = match self.pixel_index(x as u32, y as u32) {
+                    Some(idx) => idx,
+                    None => continue,
+                };
+
+                let (src_x, src_y) = (x - dx, y - dy);
+
+                let src = if (0..w).contains(&src_x) && (0..h).contains(&src_y) {
+                    self.pixel_index(src_x as u32, src_y as u32)
+                } else {
+                    None
+                };
+
+                match src {
+                    Some(src) => self.buffer.bytes_mut().copy_within(src..src + bpp, dst),
+                    None => self.buffer.bytes_mut()[dst..dst + bpp].fill(0),
+                }
+            }
+        }
+
+        self.mark_all_dirty();
+    }
+
+    /// Shift the framebuffer contents one step along a small square pattern to reduce burn-in
+    ///
+    /// Each call shifts the contents by one pixel with [`shift_pixels`](#method.shift_pixels),
+    /// moving right, down, left and then up. After four calls the contents are back where they
+    /// started, so calling this every few minutes keeps static content within a 2x2 pixel area.
+    ///
+    /// Pixels shifted off the edge of the display are lost, so content should keep a one pixel
+    /// margin, or be redrawn before each call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.set_pixel(10, 20, 0xffff);
+    ///
+    /// // Call periodically, for example once a minute
+    /// display.antiburn_tick();
+    /// display.flush().unwrap();
+    /// ```
+    pub fn antiburn_tick(&mut self) {
+        let (dx, dy) = ANTIBURN_PATTERN[usize::from(self.antiburn_step)];
+
+        self.antiburn_step = (self.antiburn_step + 1) % ANTIBURN_PATTERN.len() as u8;
+
+        self.shift_pixels(dx, dy);
+    }
+
+    /// Shrink the framebuffer contents into `out` to make a thumbnail, returning its width and
+    /// height
+    ///
+    /// Each `factor` x `factor` block of pixels, in logical (rotated) coordinates, is averaged into
+    /// one RGB565 pixel of the thumbnail. The red, green and blue channels are averaged
+    /// separately, rounding to the nearest value: `(sum + count / 2) / count`. If the display size
+    /// isn't a multiple of `factor`, the blocks along the right and bottom edges are smaller and
+    /// only the pixels they contain are averaged.
+    ///
+    /// The thumbnail is written to the start of `out` row by row, so pixel `(x, y)` is at
+    /// `out[y * width + x]`. An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument)
+    /// error is returned if `factor` is zero or `out` is too small to hold the thumbnail.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// // Quarter size thumbnail
+    /// let mut thumbnail = [0u16; 24 * 16];
+    ///
+    /// assert_eq!(display.downscale(4, &mut thumbnail).unwrap(), (24, 16));
+    /// ```
+    pub fn downscale(&self, factor: u8, out: &mut [u16]) -> Result<(u8, u8), Error<CommE, PinE>> {
+        if factor == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (w, h) = self.dimensions();
+        let factor = u32::from(factor);
+        let (w, h) = (u32::from(w), u32::from(h));
+        let (thumb_w, thumb_h) = (w.div_ceil(factor), h.div_ceil(factor));
+
+        if out.len() < (thumb_w * thumb_h) as usize {
+            return Err(Error::InvalidArgument);
+        }
+
+        for ty in 0..thumb_h {
+            for tx in 0..thumb_w {
+                let (mut r, mut g, mut b, mut count) = (0, 0, 0, 0);
+
+                for y in ty * factor..((ty + 1) * factor).min(h) {
+                    for x in tx * factor..((tx + 1) * factor).min(w) {
+                        if let Some(idx) = self.pixel_index(x, y) {
+                            let value = u32::from(self.read_pixel(idx));
+
+                            r += value >> 11;
+                            g += (value >> 5) & 0x3f;
+                            b += value & 0x1f;
+                            count += 1;
+                        }
+                    }
+                }
+
+                // Blocks with no pixels in the framebuffer storage are left black
+                let average = |sum: u32| (sum + count / 2).checked_div(count).unwrap_or(0);
+
+                out[(ty * thumb_w + tx) as usize] =
+                    ((average(r) << 11) | (average(g) << 5) | average(b)) as u16;
+            }
+        }
+
+        Ok((thumb_w as u8, thumb_h as u8))
+    }
+
+    /// Draw an anti-aliased line into the framebuffer
+    ///
+    /// This uses Xiaolin Wu's algorithm with integer coverage calculations: pixels either side of
+    /// the ideal line are blended with the existing framebuffer contents in proportion to how
+    /// close they are to it. Diagonal lines look noticeably smoother than aliased ones, which is
+    /// especially visible at this panel's low resolution. The end points are drawn at full
+    /// intensity and parts of the line that are off screen are skipped.
+    ///
+    /// Only the framebuffer is updated, so [`flush`](#method.flush) must be called afterwards. This
+    /// is much more expensive than the controller's accelerated line drawing used by
+    /// [`draw_rect_border_accel`](#method.draw_rect_border_accel): about two pixels are read,
+    /// blended and written for every step along the line, and the result then has to be sent to
+    /// the display. The accelerated command only sends a few bytes but isn't anti-aliased and
+    /// doesn't update the framebuffer.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// display.draw_line_aa(Point::new(5, 5), Point::new(90, 40), Rgb565::WHITE);
+    /// display.flush().unwrap();
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn draw_line_aa(&mut self, start: Point, end: Point, color: Rgb565) {
+        // Always draw downwards
+        let (start, end) = if start.y > end.y {
+            (end, start)
+        } else {
+            (start, end)
+        };
+
+        let Point { mut x, mut y } = start;
+        let dx = (end.x - x).abs();
+        let dy = end.y - y;
+        let x_dir = if end.x >= x { 1 } else { -1 };
+
+        self.blend_pixel(x, y, color, 255);
+
+        // Horizontal, vertical and diagonal lines don't need any anti-aliasing
+        if dx == 0 || dy == 0 || dx == dy {
+            let steps = dx.max(dy);
+            let (step_x, step_y) = ((dx > 0) as i32 * x_dir, (dy > 0) as i32);
+
+            for _ in 0..steps {
+                x += step_x;
+                y += step_y;
+
+                self.blend_pixel(x, y, color, 255);
+            }
+
+            return;
+        }
+
+        // Fractional distance from the ideal line, as a 16 bit fixed point number. The top 8 bits
+        // are the coverage of the pixel on the far side of the line.
+        let mut error: u16 = 0;
+
+        if dy > dx {
+            let error_step = ((dx as u32) << 16) / dy as u32;
+
+            for _ in 1..dy {
+                let (next, carry) = error.overflowing_add(error_step as u16);
+                error = next;
+
+                if carry {
+                    x += x_dir;
+                }
+
+                y += 1;
+
+                let coverage = (error >> 8) as u8;
+
+                self.blend_pixel(x, y, color, 255 - coverage);
+                self.blend_pixel(x + x_dir, y, color, coverage);
+            }
+        } else {
+            let error_step = ((dy as u32) << 16) / dx as u32;
+
+            for _ in 1..dx {
+                let (next, carry) = error.overflowing_add(error_step as u16);
+                error = next;
+
+                if carry {
+                    y += 1;
+                }
+
+                x += x_dir;
+
+                let coverage = (error >> 8) as u8;
+
+                self.blend_pixel(x, y, color, 255 - coverage);
+                self.blend_pixel(x, y + 1, color, coverage);
+            }
+        }
+
+        self.blend_pixel(end.x, end.y, color, 255);
+    }
+
+    /// Blend `color` over the pixel at `(x, y)`, where a `coverage` of 255 replaces it completely
+    ///
+    /// Off screen coordinates are ignored.
+    #[cfg(feature = "graphics")]
+    fn blend_pixel(&mut self, x: i32, y: i32, color: Rgb565, coverage: u8) {
+        if x < 0 || y < 0 {
+            return;
+        }
+
+        let (x, y) = (x as u32, y as u32);
+
+        if let Some(idx) = self.pixel_index(x, y) {
+            let background = Rgb565::from(RawU16::new(self.read_pixel(idx)));
+
+            let coverage = u16::from(coverage);
+            let mix = |fg: u8, bg: u8| {
+                ((u16::from(fg) * coverage + u16::from(bg) * (255 - coverage) + 127) / 255) as u8
+            };
+
+            let blended = Rgb565::new(
+                mix(color.r(), background.r()),
+                mix(color.g(), background.g()),
+                mix(color.b(), background.b()),
+            );
+
+            self.set_pixel(x, y, RawU16::from(blended).into_inner());
+        }
+    }
+
+    /// Fill one or more full rows of the framebuffer, starting at `start_row`
+    ///
+    /// `data` holds RGB565 pixel values, left to right then top to bottom, and its length must be
+    /// a whole number of rows (a multiple of the display width in the current rotation). An error
+    /// is returned if it isn't, or if the rows would extend past the bottom of the display.
+    ///
+    /// Full rows are contiguous in the framebuffer, so this is much faster than setting each pixel
+    /// individually. The exception is [software rotation](#method.set_software_rotation) by 90 or
+    /// 270 degrees, where each pixel is written separately.
+    ///
+    /// Use [`flush_rows`](#method.flush_rows) to send only the updated rows to the display.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Draw a red band 4 rows tall, 10 rows from the top
+    /// display.set_rows(10, &[0xf800; 96 * 4]).unwrap();
+    /// display.flush_rows(10, 4).unwrap();
+    ///
+    /// // Data must be a whole number of rows
+    /// assert!(display.set_rows(10, &[0xf800; 100]).is_err());
+    /// ```
+    pub fn set_rows(&mut self, start_row: u8, data: &[u16]) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+        let width = usize::from(w);
+
+        if data.len() % width != 0 || usize::from(start_row) + data.len() / width > usize::from(h) {
+            return Err(Error::InvalidArgument);
+        }
+
+        if self.config.software_rotation {
+            for (i, value) in data.iter().enumerate() {
+                let x = (i % width) as u32;
+                let y = u32::from(start_row) + (i / width) as u32;
+
+                self.set_pixel(x, y, *value);
+            }
+        } else {
+            let bpp = self.bytes_per_pixel();
+            let start = usize::from(start_row) * width * bpp;
+
+            for (i, value) in data.iter().enumerate() {
+                self.write_pixel(start + i * bpp, *value);
+            }
+
+            if !data.is_empty() {
+                let end_row = start_row + (data.len() / width) as u8 - 1;
+
+                self.mark_dirty((0, start_row), (w - 1, end_row));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send one or more full rows of the framebuffer to the display
+    ///
+    /// Only `num_rows` rows starting at `start_row` are sent, which is quicker than a full
+    /// [`flush`](#method.flush) when only a band of the display has changed. An error is returned
+    /// if the rows extend past the bottom of the display.
+    ///
+    /// This leaves the draw area set to the flushed rows.
+    pub fn flush_rows(&mut self, start_row: u8, num_rows: u8) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+
+        if num_rows == 0 || u16::from(start_row) + u16::from(num_rows) > u16::from(h) {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.flush_area((0, start_row), (w - 1, start_row + num_rows - 1))
+    }
+
+    /// Send a rectangular region of the framebuffer to the display
+    ///
+    /// Only the pixels between the inclusive corners `top_left` and `bottom_right` are sent, which
+    /// is quicker than a full [`flush`](#method.flush) when the caller knows exactly what changed,
+    /// for example a single column of a scrolling plot. Coordinates are logical, like
+    /// [`set_pixel`](#method.set_pixel), and are mapped to the controller's address space and
+    /// framebuffer layout for the current rotation. Rows of the region that are contiguous in the
+    /// framebuffer are sent in a single write.
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and
+    /// nothing is sent if either corner is outside [`dimensions`](#method.dimensions), or
+    /// `bottom_right` is above or left of `top_left`.
+    ///
+    /// Unlike [`flush_dirty`](#method.flush_dirty), this doesn't change the tracked dirty region.
+    /// This leaves the draw area set to the flushed region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Draw a new sample into column 40 and send only that column
+    /// display.set_pixel(40, 12, 0x07e0);
+    /// display.flush_region((40, 0), (40, 63)).unwrap();
+    /// ```
+    pub fn flush_region(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let ((x0, y0), (x1, y1)) = (top_left, bottom_right);
+        let (w, h) = self.dimensions();
+
+        if x1 >= w || y1 >= h || x0 > x1 || y0 > y1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.flush_area(top_left, bottom_right)
+    }
+
+    /// Send a rectangular region of the framebuffer to the display
+    ///
+    /// Corners are inclusive, in logical (rotated) coordinates, and must be on screen.
+    fn flush_area(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        // Partial flushes index into the buffer by row, so need a full frame as much as `flush`
+        self.check_buffer_len()?;
+
+        let ((x0, y0), (x1, y1)) = (top_left, bottom_right);
+
+        // Controller window, the buffer rows and columns that make it up, and the buffer stride
+        let (window_start, window_end, rows, columns, stride) = if self.config.software_rotation {
+            let (ax, ay) = self.to_unrotated(u32::from(x0), u32::from(y0));
+            let (bx, by) = self.to_unrotated(u32::from(x1), u32::from(y1));
+
+            let (c0, c1) = (ax.min(bx) as u8, ax.max(bx) as u8);
+            let (r0, r1) = (ay.min(by) as u8, ay.max(by) as u8);
+
+            ((c0, r0), (c1, r1), (r0, r1), (c0, c1), DISPLAY_WIDTH)
+        } else {
+            // The buffer is stored in logical row-major order. For 90 and 270 degree rotations the
+            // controller increments vertically, so logical rows are sent as controller columns.
+            let (w, _) = self.dimensions();
+
+            match self.config.rotation {
+                DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                    ((x0, y0), (x1, y1), (y0, y1), (x0, x1), w)
+                }
+                DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                    ((y0, x0), (y1, x1), (y0, y1), (x0, x1), w)
+                }
+            }
+        };
+
+        self.set_draw_area(window_start, window_end)?;
+
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        let bpp = self.bytes_per_pixel();
+        let stride = usize::from(stride) * bpp;
+        let start = usize::from(columns.0) * bpp;
+        let end = (usize::from(columns.1) + 1) * bpp;
+
+        if start == 0 && end == stride {
+            // Full rows are contiguous so can be sent in one go
+            let rows = usize::from(rows.0) * stride..(usize::from(rows.1) + 1) * stride;
+
+            self.send_buffer(rows)?;
+        } else {
+            for row in rows.0..=rows.1 {
+                let offset = usize::from(row) * stride;
+
+                self.send_buffer(offset + start..offset + end)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send a range of the framebuffer as pixel data
+    ///
+    /// The DC pin must already be set to data mode. The controller expects the high byte of each
+    /// RGB565 pixel first, so a little endian buffer is byte swapped in small chunks as it is
+    /// sent. No write is longer than `max_transfer_size`.
+    fn send_buffer(&mut self, range: Range<usize>) -> Result<(), Error<CommE, PinE>> {
+        let swap = self.byte_order == ByteOrder::LittleEndian
+            && self.config.color_mode == ColorMode::CM65k;
+
+        if !swap {
+            return write_chunked(
+                &mut self.spi,
+                &self.buffer.bytes()[range],
+                self.max_transfer_size,
+            );
+        }
+
+        let mut chunk = [0u8; 64];
+
+        // Chunks must hold whole pixels to be swapped
+        let chunk_len = chunk.len().min(self.max_transfer_size & !1);
+
+        for pixels in self.buffer.bytes()[range].chunks(chunk_len) {
+            let chunk = &mut chunk[0..pixels.len()];
+
+            for (out, pixel) in chunk.chunks_exact_mut(2).zip(pixels.chunks_exact(2)) {
+                out[0] = pixel[1];
+                out[1] = pixel[0];
+            }
+
+            self.spi.write(chunk).map_err(Error::Comm)?;
+        }
+
+        Ok(())
+    }
+
+    /// Grow the dirty region to include the inclusive logical rectangle
+    fn mark_dirty(&mut self, top_left: (u8, u8), bottom_right: (u8, u8)) {
+        self.dirty = Some(match self.dirty {
+            Some(((x0, y0), (x1, y1))) => (
+                (x0.min(top_left.0), y0.min(top_left.1)),
+                (x1.max(bottom_right.0), y1.max(bottom_right.1)),
+            ),
+            None => (top_left, bottom_right),
+        });
+    }
+
+    /// Number of framebuffer bytes used by each pixel in the current colour mode
+    fn bytes_per_pixel(&self) -> usize {
+        match self.config.color_mode {
+            ColorMode::CM256 => 1,
+            ColorMode::CM65k => 2,
+        }
+    }
+
+    /// Number of framebuffer bytes used by a full frame in the current colour mode
+    fn frame_len(&self) -> usize {
+        usize::from(DISPLAY_WIDTH) * usize::from(DISPLAY_HEIGHT) * self.bytes_per_pixel()
+    }
+
+    /// Convert an RGB565 value to the framebuffer format. Only the first `bytes_per_pixel()`
+    /// bytes are used.
+    fn encode_pixel(&self, value: u16) -> [u8; 2] {
+        match (self.config.color_mode, self.byte_order) {
+            (ColorMode::CM256, _) => [rgb565_to_rgb332(value), 0],
+            (ColorMode::CM65k, ByteOrder::BigEndian) => value.to_be_bytes(),
+            (ColorMode::CM65k, ByteOrder::LittleEndian) => value.to_le_bytes(),
+        }
+    }
+
+    /// Write a pixel value into the framebuffer at the given byte offset
+    fn write_pixel(&mut self, idx: usize, value: u16) {
+        if self.dithers() {
+            let width = usize::from(DISPLAY_WIDTH);
+
+            self.buffer.bytes_mut()[idx] =
+                rgb565_to_rgb332_dithered(value, idx % width, idx / width);
+
+            return;
+        }
+
+        let bytes = self.encode_pixel(value);
+
+        match self.config.color_mode {
+            ColorMode::CM256 => self.buffer.bytes_mut()[idx] = bytes[0],
+            ColorMode::CM65k => self.buffer.bytes_mut()[idx..idx + 2].copy_from_slice(&bytes),
+        }
+    }
+
+    /// Read the RGB565 value of the pixel at the given byte offset
+    fn read_pixel(&self, idx: usize) -> u16 {
+        let buffer = self.buffer.bytes();
+
+        // In 256 colour mode the last pixel may be the last byte of the buffer
+        if self.config.color_mode == ColorMode::CM256 {
+            return rgb332_to_rgb565(buffer[idx]);
+        }
+
+        let bytes = [buffer[idx], buffer[idx + 1]];
+
+        match self.byte_order {
+            ByteOrder::BigEndian => u16::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => u16::from_le_bytes(bytes),
+        }
+    }
+
+    /// Set `len` pixels in a horizontal run starting at the on-screen logical coordinate `(x, y)`
+    ///
+    /// The run must not extend past the right hand edge of the display.
+    #[cfg(feature = "graphics")]
+    fn fill_span(&mut self, x: u32, y: u32, len: u32, value: u16) {
+        if self.config.software_rotation {
+            // Logical rows aren't contiguous in the unrotated buffer
+            for x in x..x + len {
+                self.set_pixel(x, y, value);
+            }
+
+            return;
+        }
+
+        if let Some(start) = self.pixel_index(x, y) {
+            let bytes = self.encode_pixel(value);
+            let end = start + len as usize * self.bytes_per_pixel();
+
+            if self.dithers() {
+                // Each pixel gets a different threshold, so the run can't be a single fill
+                for idx in start..end {
+                    self.write_pixel(idx, value);
+                }
+
+                self.mark_dirty((x as u8, y as u8), ((x + len - 1) as u8, y as u8));
+
+                return;
+            }
+
+            let span = match self.buffer.bytes_mut().get_mut(start..end) {
+                Some(span) => span,
+                None => return,
+            };
+
+            match self.config.color_mode {
+                ColorMode::CM256 => span.fill(bytes[0]),
+                ColorMode::CM65k => {
+                    for pixel in span.chunks_exact_mut(2) {
+                        pixel.copy_from_slice(&bytes);
+                    }
+                }
+            }
+
+            self.mark_dirty((x as u8, y as u8), ((x + len - 1) as u8, y as u8));
+        }
+    }
+
+    /// Map logical coordinates onto the unrotated 96x64 buffer used by software rotation
+    fn to_unrotated(&self, x: u32, y: u32) -> (u32, u32) {
+        let max_x = u32::from(DISPLAY_WIDTH) - 1;
+        let max_y = u32::from(DISPLAY_HEIGHT) - 1;
+
+        match self.config.rotation {
+            DisplayRotation::Rotate0 => (x, y),
+            DisplayRotation::Rotate90 => (max_x - y, x),
+            DisplayRotation::Rotate180 => (max_x - x, max_y - y),
+            DisplayRotation::Rotate270 => (y, max_y - x),
+        }
+    }
+
+    /// Get the framebuffer byte offset of the pixel at `(x, y)`, or `None` if it is off screen
+    ///
+    /// Coordinates are logical, so the current rotation (hardware or software) is taken into
+    /// account. This allows rotation aware code to work directly on the slice returned by
+    /// [`buffer_mut`](#method.buffer_mut).
+    ///
+    /// In the default 65k colour mode each pixel is two bytes of RGB565 starting at the returned
+    /// offset, high byte first. With a little endian [`ByteOrder`](./enum.ByteOrder.html) the low
+    /// byte comes first instead, and in 256 colour mode the pixel is the single RGB332 byte at the
+    /// offset. See [`init_with`](#method.init_with).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, DisplayRotation::Rotate0);
+    ///
+    /// assert_eq!(display.pixel_index(1, 2), Some((2 * 96 + 1) * 2));
+    /// assert_eq!(display.pixel_index(96, 0), None);
+    ///
+    /// // Set the pixel at (1, 2) to white
+    /// let idx = display.pixel_index(1, 2).unwrap();
+    /// display.buffer_mut()[idx..idx + 2].copy_from_slice(&0xffffu16.to_be_bytes());
+    ///
+    /// // Rows are 64 pixels wide when rotated by 90 degrees
+    /// display.set_rotation(DisplayRotation::Rotate90).unwrap();
+    ///
+    /// assert_eq!(display.pixel_index(1, 2), Some((2 * 64 + 1) * 2));
+    /// ```
+    pub fn pixel_index(&self, x: u32, y: u32) -> Option<usize> {
+        let bpp = self.bytes_per_pixel();
+        let (w, h) = self.dimensions();
+
+        // Both axes must be checked, as an x past the end of a row would otherwise land on the
+        // start of the next one
+        if x >= u32::from(w) || y >= u32::from(h) {
+            return None;
+        }
+
+        let idx = if self.config.software_rotation {
+            let (x, y) = self.to_unrotated(x, y);
+
+            ((y as usize) * DISPLAY_WIDTH as usize) + (x as usize)
+        } else {
+            // The buffer is stored in logical row-major order, so rows are as wide as the rotated
+            // display
+            ((y as usize) * w as usize) + (x as usize)
+        } * bpp;
+
+        // A borrowed buffer may be too short
+        if idx + bpp > self.buffer.bytes().len() {
+            return None;
+        }
+
+        Some(idx)
+    }
+
+    /// Initialise display, setting sensible defaults and rotation
+    ///
+    /// After power up the controller's memory contains random noise. To avoid showing it, a full
+    /// black frame is sent before the display is turned on at the end of initialisation. This
+    /// adds one frame of SPI traffic to `init`, and leaves the framebuffer untouched. It can be
+    /// disabled with [`set_clear_on_init`](#method.set_clear_on_init).
+    pub fn init(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.init_off()?;
+
+        if self.clear_on_init {
+            self.flush_solid(0)?;
+        }
+
+        self.turn_on()
+    }
+
+    /// Initialise display like [`init`](#method.init), filling it with `color` before turning it
+    /// on
+    ///
+    /// Instead of sending a frame, the controller's accelerated rectangle drawing fills its memory
+    /// with `color` while the display is still off, so the panel comes up showing a known
+    /// background. This sends 15 command bytes instead of a 12,288 byte frame. `delay` is used to
+    /// wait 3ms for the fill to finish, as the controller can't accept commands while drawing.
+    ///
+    /// Accelerated drawing uses 6 bits per colour channel, so the 5 bit red and blue channels of
+    /// `color` are doubled and the 6 bit green channel is used as-is. The framebuffer is also
+    /// filled with `color`, so it matches what's on the display.
+    ///
+    /// This requires the `graphics` feature to be enabled (on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display.init_clearing(Rgb565::BLUE, &mut delay).unwrap();
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn init_clearing<DELAY>(
+        &mut self,
+        color: Rgb565,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        let color = RawU16::from(color).into_inner();
+
+        self.init_off()?;
+
+        Command::FillMode(true).send(&mut self.spi, &mut self.dc)?;
+        Command::DrawRect(0, 0, DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1, color, color)
+            .send(&mut self.spi, &mut self.dc)?;
+        delay.delay_ms(ACCEL_FILL_DELAY_MS);
+        Command::FillMode(false).send(&mut self.spi, &mut self.dc)?;
+
+        let (w, h) = self.dimensions();
+
+        for y in 0..u32::from(h) {
+            self.fill_span(0, y, u32::from(w), color);
+        }
+
+        // The display already shows the framebuffer contents
+        self.dirty = None;
+
+        self.turn_on()
+    }
+
+    /// Initialise display like [`init`](#method.init), waiting after commands some panels need
+    /// time to settle after
+    ///
+    /// Most panels work with [`init`](#method.init), which sends its commands back to back. Some
+    /// clone modules come up unstable or flickering unless given time after changing analogue
+    /// settings. This sends the same commands as `init`, using `delay` to wait:
+    ///
+    /// * 10ms after setting the master current and contrast, which change the current driving the
+    ///   panel. The datasheet doesn't give a settling time for these, so this is a conservative
+    ///   value found to work with panels that need it.
+    /// * 100ms after turning the display on. The datasheet's power on sequence notes the segment
+    ///   and common outputs only turn on 100ms after the display on command (0xAF), so nothing
+    ///   sent in that time is shown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display.init_with_delay(&mut delay).unwrap();
+    /// ```
+    pub fn init_with_delay<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.send_setup(|ms| delay.delay_ms(ms))?;
+
+        if self.clear_on_init {
+            self.flush_solid(0)?;
+        }
+
+        self.turn_on()?;
+        delay.delay_ms(DISPLAY_ON_DELAY_MS);
+
+        Ok(())
+    }
+
+    /// Initialise display like [`init`](#method.init), then flash the whole panel to show it's
+    /// working
+    ///
+    /// The SSD1331 can't be read from over SPI, so there's no way for the driver to check the
+    /// panel is connected and responding. This gives a visual check instead: after initialising,
+    /// every pixel is switched fully on then off twice, 200ms at a time, using the controller's
+    /// "entire display on" and "entire display off" modes. The display then returns to showing
+    /// its memory in normal or inverted mode, as before.
+    ///
+    /// This is only a visual test for use during development. The flashes only show that the
+    /// panel received the commands; nothing is read back, and errors are only returned if the SPI
+    /// or DC pin report one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display.init_with_self_test(&mut delay).unwrap();
+    /// ```
+    pub fn init_with_self_test<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        self.init()?;
+
+        for _ in 0..2 {
+            Command::DisplayMode(DisplayMode::AllOn).send(&mut self.spi, &mut self.dc)?;
+            delay.delay_ms(SELF_TEST_FLASH_MS);
+            Command::DisplayMode(DisplayMode::AllOff).send(&mut self.spi, &mut self.dc)?;
+            delay.delay_ms(SELF_TEST_FLASH_MS);
+        }
+
+        self.send_display_mode()
+    }
+
+    /// Enable or disable clearing the controller's memory in [`init`](#method.init)
+    ///
+    /// This is the runtime equivalent of [`with_clear_on_init`](#method.with_clear_on_init), for
+    /// when the choice is only known after the display is created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// // The display was left running with valid contents, so don't clear it
+    /// display.set_clear_on_init(false);
+    /// display.init().unwrap();
+    /// ```
+    pub fn set_clear_on_init(&mut self, enabled: bool) {
+        self.clear_on_init = enabled;
+    }
+
+    /// Enable or disable ordered dithering of pixels drawn in 256 colour mode
+    ///
+    /// In 256 colour mode each RGB565 colour is reduced to RGB332 as it is drawn into the
+    /// framebuffer. With dithering disabled (the default) the low bits are truncated, which keeps
+    /// text and flat colours crisp but turns smooth gradients into visible bands. With dithering
+    /// enabled a 4x4 Bayer matrix keyed on each pixel's framebuffer position nudges colours up or
+    /// down, so in between shades are approximated by a fine pattern of the nearest two.
+    ///
+    /// This has no effect in 65k colour mode. Pixels already in the framebuffer are not changed,
+    /// and colours sent straight to the display by [`flush_solid`](#method.flush_solid) and
+    /// [`draw_image_raw`](#method.draw_image_raw) are not dithered.
+    ///
+    /// Dithering costs a table lookup and a few additions per pixel, and solid fills and clears
+    /// are written a pixel at a time instead of as a single byte fill.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new_256(spi, dc, Rotate0);
+    ///
+    /// display.set_dithering(true);
+    ///
+    /// // Draw a horizontal gradient from black to green
+    /// for x in 0..96 {
+    ///     for y in 0..64 {
+    ///         display.set_pixel(x, y, ((x * 63 / 95) as u16) << 5);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_dithering(&mut self, enabled: bool) {
+        self.dithering = enabled;
+    }
+
+    /// Whether pixels written to the framebuffer are currently dithered
+    fn dithers(&self) -> bool {
+        self.dithering && self.config.color_mode == ColorMode::CM256
+    }
+
+    /// Initialise display like [`init`](#method.init), choosing the colour mode and framebuffer
+    /// byte order
+    ///
+    /// * [`ColorMode::CM65k`](./enum.ColorMode.html#variant.CM65k) stores each pixel as two bytes
+    ///   of RGB565 in the given byte order.
+    /// * [`ColorMode::CM256`](./enum.ColorMode.html#variant.CM256) stores each pixel as a single
+    ///   RGB332 byte, so only the first half of the framebuffer is used and flushes send half as
+    ///   much data. RGB565 values passed to [`set_pixel`](#method.set_pixel) and other drawing
+    ///   methods are truncated to 3 bits of red and green and 2 bits of blue. The byte order has no
+    ///   effect.
+    ///
+    /// The framebuffer format stays fixed until `init_with` is called again, which clears the
+    /// framebuffer as its old contents are no longer meaningful. [`init`](#method.init) keeps the
+    /// current format, which defaults to 65k colours in big endian order (little endian if the
+    /// `le-buffer` feature is enabled), or 256 colours for displays created with
+    /// [`new_256`](#method.new_256).
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and
+    /// nothing is changed if the framebuffer storage is too small for a full frame in
+    /// `color_mode`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{ByteOrder, ColorMode, DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display
+    ///     .init_with(ColorMode::CM256, ByteOrder::BigEndian)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(display.buffer_mut().len(), 96 * 64);
+    /// ```
+    pub fn init_with(
+        &mut self,
+        color_mode: ColorMode,
+        byte_order: ByteOrder,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let bytes_per_pixel = match color_mode {
+            ColorMode::CM256 => 1,
+            ColorMode::CM65k => 2,
+        };
+
+        if self.buffer.bytes().len() < BUF_SIZE_256 * bytes_per_pixel {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.config.color_mode = color_mode;
+        self.byte_order = byte_order;
+        self.clear();
+
+        self.init()
+    }
+
+    /// Initialise display like [`init`](#method.init), but leave it turned off
+    ///
+    /// This allows the first frame to be sent before anything is visible, so the random contents
+    /// of the controller's memory at power up are never shown. The recommended startup order is:
+    ///
+    /// 1. [`reset`](#method.reset) the display
+    /// 2. Call `init_off()`
+    /// 3. Draw the first frame and [`flush`](#method.flush) it
+    /// 4. Call [`turn_on`](#method.turn_on)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    ///
+    /// display.init_off().unwrap();
+    ///
+    /// // Draw the first frame while the display is still off
+    /// display.set_pixel(10, 20, 0xffff);
+    /// display.flush().unwrap();
+    ///
+    /// display.turn_on().unwrap();
+    /// ```
+    pub fn init_off(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.send_setup(|_| {})
+    }
+
+    /// Send the setup commands used by `init_off`, calling `settle` with a time in milliseconds to
+    /// wait after the master current is set
+    fn send_setup(&mut self, mut settle: impl FnMut(u8)) -> Result<(), Error<CommE, PinE>> {
+        // With software rotation the controller is left unrotated
+        let remap_rotation = if self.config.software_rotation {
+            DisplayRotation::Rotate0
+        } else {
+            self.config.rotation
+        };
+
+        self.turn_off()?;
+        let (fosc, divide) = self.config.display_clock;
+
+        Command::DisplayClockDiv(fosc, divide).send(&mut self.spi, &mut self.dc)?;
+        Command::Multiplex(self.config.multiplex).send(&mut self.spi, &mut self.dc)?;
+        Command::StartLine(self.config.start_line).send(&mut self.spi, &mut self.dc)?;
+        Command::DisplayOffset(self.config.display_offset).send(&mut self.spi, &mut self.dc)?;
+
+        self.send_remap(remap_rotation)?;
+
+        self.send_brightness()?;
+        settle(CURRENT_SETTLE_MS);
+
+        let Config {
+            precharge_period,
+            precharge_voltage,
+            vcomh,
+            gamma,
+            ..
+        } = self.config;
+
+        Command::PreChargePeriod(precharge_period.0, precharge_period.1)
+            .send(&mut self.spi, &mut self.dc)?;
+
+        if let Some(level) = precharge_voltage {
+            Command::PreChargeVoltage(level).send(&mut self.spi, &mut self.dc)?;
+        }
+
+        Command::VcomhDeselect(vcomh).send(&mut self.spi, &mut self.dc)?;
+
+        if let Some(table) = gamma {
+            Command::GrayScaleTable(table).send(&mut self.spi, &mut self.dc)?;
+        }
+
+        self.send_display_mode()
+    }
+
+    /// Send the display mode for the configured blanking and inversion
+    fn send_display_mode(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let mode = if self.config.all_on {
+            DisplayMode::AllOn
+        } else if self.config.blanked {
+            DisplayMode::AllOff
+        } else if self.config.inverted {
+            DisplayMode::Inverse
+        } else {
+            DisplayMode::Normal
+        };
+
+        Command::DisplayMode(mode).send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    ///
+    /// # Examples
+    ///
+    /// ## No rotation
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let display = Ssd1331::new(spi, dc, DisplayRotation::Rotate0);
+    ///
+    /// assert_eq!(display.dimensions(), (96, 64));
+    /// ```
+    ///
+    /// ## 90 degree rotation rotation
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let display = Ssd1331::new(spi, dc, DisplayRotation::Rotate90);
+    ///
+    /// assert_eq!(display.dimensions(), (64, 96));
+    /// ```
+    pub fn dimensions(&self) -> (u8, u8) {
+        match self.config.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                (DISPLAY_WIDTH, DISPLAY_HEIGHT)
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                (DISPLAY_HEIGHT, DISPLAY_WIDTH)
+            }
+        }
+    }
+
+    /// Set the display rotation
+    ///
+    /// The rotation is applied by the controller's address remapping, so the framebuffer is sent
+    /// in the order the controller expects for the new rotation. This disables any rotation set
+    /// with [`set_software_rotation`](#method.set_software_rotation).
+    ///
+    /// The remapping also applies to controller addresses passed to methods like
+    /// [`set_draw_area`](#method.set_draw_area). See the
+    /// [crate level documentation](./index.html#rotation-and-coordinate-spaces) for how each
+    /// method's coordinates are affected by rotation.
+    pub fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error<CommE, PinE>> {
+        self.config.rotation = rot;
+        self.config.software_rotation = false;
+        self.mark_all_dirty();
+
+        self.send_remap(rot)
+    }
+
+    /// Set the display rotation like [`set_rotation`](#method.set_rotation), returning the new
+    /// display dimensions
+    ///
+    /// This saves a separate call to [`dimensions`](#method.dimensions) when laying out content for
+    /// the new rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, DisplayRotation::Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// let (width, height) = display
+    ///     .set_rotation_dims(DisplayRotation::Rotate90)
+    ///     .unwrap();
+    ///
+    /// assert_eq!((width, height), (64, 96));
+    /// ```
+    pub fn set_rotation_dims(
+        &mut self,
+        rot: DisplayRotation,
+    ) -> Result<(u8, u8), Error<CommE, PinE>> {
+        self.set_rotation(rot)?;
+
+        Ok(self.dimensions())
+    }
+
+    /// Set the display rotation without reconfiguring the controller
+    ///
+    /// Unlike [`set_rotation`](#method.set_rotation), this sends no commands to the display. Only
+    /// the coordinate mapping used by [`set_pixel`](#method.set_pixel) and the value returned by
+    /// [`dimensions`](#method.dimensions) change. Pixels are stored in the unrotated 96x64 layout,
+    /// and [`flush`](#method.flush) sends the buffer as-is.
+    ///
+    /// This is useful when the controller's remap must stay at its default, for example when
+    /// content is already pre-rotated or when controller-side coordinates must not change. The
+    /// controller is expected to be in its unrotated configuration; `init()` leaves it that way
+    /// while software rotation is active.
+    ///
+    /// Accelerated drawing methods such as
+    /// [`draw_rect_border_accel`](#method.draw_rect_border_accel) still take logical coordinates,
+    /// which are mapped to the unrotated controller addresses. Methods taking controller addresses
+    /// directly, such as [`set_draw_area`](#method.set_draw_area), always address the unrotated
+    /// panel. See the [crate level documentation](./index.html#rotation-and-coordinate-spaces) for
+    /// more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, DisplayRotation::Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// display.set_software_rotation(DisplayRotation::Rotate90);
+    ///
+    /// assert_eq!(display.dimensions(), (64, 96));
+    /// ```
+    pub fn set_software_rotation(&mut self, rot: DisplayRotation) {
+        self.config.rotation = rot;
+        self.config.software_rotation = true;
+        self.mark_all_dirty();
+    }
+
+    /// Send the remap command for the given rotation
+    fn send_remap(&mut self, rot: DisplayRotation) -> Result<(), Error<CommE, PinE>> {
+        let color_mode = self.config.color_mode;
+
+        // Changing the address increment mode moves the address pointer
+        self.window_full = false;
+
+        match rot {
+            DisplayRotation::Rotate0 => {
+                Command::RemapAndColorDepth(
+                    false,
+                    false,
+                    color_mode,
+                    AddressIncrementMode::Horizontal,
+                )
+                .send(&mut self.spi, &mut self.dc)?;
+            }
+            DisplayRotation::Rotate90 => {
+                Command::RemapAndColorDepth(
+                    true,
+                    false,
+                    color_mode,
+                    AddressIncrementMode::Vertical,
+                )
+                .send(&mut self.spi, &mut self.dc)?;
+            }
+            DisplayRotation::Rotate180 => {
+                Command::RemapAndColorDepth(
+                    true,
+                    true,
+                    color_mode,
+                    AddressIncrementMode::Horizontal,
+                )
+                .send(&mut self.spi, &mut self.dc)?;
+            }
+            DisplayRotation::Rotate270 => {
+                Command::RemapAndColorDepth(
+                    false,
+                    true,
+                    color_mode,
+                    AddressIncrementMode::Vertical,
+                )
+                .send(&mut self.spi, &mut self.dc)?;
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Set the overall brightness of the display from 0 (dimmest) to 255 (brightest)
+    ///
+    /// The SSD1331 has two ways to control brightness: a master current setting with 16 coarse
+    /// steps shared by all channels, and a per channel contrast from 0 to 255. This method splits
+    /// `level` between them to keep as much contrast resolution as possible:
+    ///
+    /// * The master current is set to the smallest of its 16 steps that is at least
+    ///   `(level + 1) / 256` of full scale
+    /// * Each channel's contrast is scaled down from its full brightness value in
+    ///   [`Config::contrast`](./struct.Config.html#structfield.contrast) to make up the
+    ///   difference
+    ///
+    /// Because every channel is scaled by the same factor, the colour balance set by the per
+    /// channel contrast is preserved. At 255 the contrast is sent unchanged with the master current
+    /// at its maximum, which is what [`init`](#method.init) uses by default. The level is kept and
+    /// reapplied by later calls to `init`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Dim the display to roughly a quarter of full brightness
+    /// display.set_brightness(63).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().brightness, 63);
+    /// ```
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<CommE, PinE>> {
+        self.config.brightness = level;
+
+        self.send_brightness()
+    }
+
+    /// Set the master current of the display from 0 (dimmest) to 15 (brightest)
+    ///
+    /// The master current scales all three channels together, so this dims the display in 16
+    /// coarse steps without changing the colour balance of the per channel contrast. Values above
+    /// 15 are clamped to 15.
+    ///
+    /// This is [`set_brightness`](#method.set_brightness) restricted to the levels where the
+    /// contrast is sent unscaled, so the full contrast from
+    /// [`Config::contrast`](./struct.Config.html#structfield.contrast) is sent along with the
+    /// master current, and the matching level is stored in
+    /// [`Config::brightness`](./struct.Config.html#structfield.brightness) to be reapplied by later
+    /// calls to [`init`](#method.init).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Dim for a dark room
+    /// display.set_master_current(3).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().brightness, 63);
+    /// ```
+    pub fn set_master_current(&mut self, current: u8) -> Result<(), Error<CommE, PinE>> {
+        self.set_brightness(current.min(15) * 16 + 15)
+    }
+
+    /// Set the Vcomh deselect level
+    ///
+    /// This is the voltage unselected rows are held at, as a fraction of Vcc. It affects perceived
+    /// brightness and flicker: too low a level can look washed out or flicker, and too high can
+    /// look dark. The best level depends on the panel; the default,
+    /// [`VcomhLevel::V071`](./enum.VcomhLevel.html#variant.V071), suits most modules.
+    ///
+    /// The level is stored in [`Config::vcomh`](./struct.Config.html#structfield.vcomh) and
+    /// reapplied by later calls to [`init`](#method.init). Use
+    /// [`with_vcomh`](#method.with_vcomh) to set it before the display is initialised.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331, VcomhLevel};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// display.set_vcomh(VcomhLevel::V083).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().vcomh, VcomhLevel::V083);
+    /// ```
+    pub fn set_vcomh(&mut self, level: VcomhLevel) -> Result<(), Error<CommE, PinE>> {
+        Command::VcomhDeselect(level).send(&mut self.spi, &mut self.dc)?;
+        self.config.vcomh = level;
+
+        Ok(())
+    }
+
+    /// Set the precharge voltage, from 0 (0.1 x Vcc) to 31 (0.5 x Vcc)
+    ///
+    /// The precharge voltage is the level each pixel is charged to before it is driven. Adjusting
+    /// it, usually along with the precharge period, can reduce smearing in fast animations or
+    /// scrolling on some modules. Values above 31 are clamped to 31.
+    ///
+    /// The level is stored in
+    /// [`Config::precharge_voltage`](./struct.Config.html#structfield.precharge_voltage) and
+    /// reapplied by later calls to [`init`](#method.init). Use
+    /// [`with_precharge_voltage`](#method.with_precharge_voltage) to set it before the display is
+    /// initialised.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// display.set_precharge_voltage(0x1D).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().precharge_voltage, Some(0x1D));
+    /// ```
+    pub fn set_precharge_voltage(&mut self, level: u8) -> Result<(), Error<CommE, PinE>> {
+        let level = level.min(31);
+
+        Command::PreChargeVoltage(level).send(&mut self.spi, &mut self.dc)?;
+        self.config.precharge_voltage = Some(level);
+
+        Ok(())
+    }
+
+    /// Set the oscillator frequency and display clock divide ratio, each from 0 to 15
+    ///
+    /// Higher `fosc` values run the internal oscillator faster, and the display clock is the
+    /// oscillator divided by `divide + 1`. Together they set the refresh rate: lowering it can
+    /// reduce electromagnetic interference with nearby radios, at the cost of more visible
+    /// flicker. Only the low 4 bits of each are used.
+    ///
+    /// The default used by [`init`](#method.init) is `fosc` 15 and `divide` 0, the fastest
+    /// refresh. The setting is stored in
+    /// [`Config::display_clock`](./struct.Config.html#structfield.display_clock) and reapplied by
+    /// later calls to `init`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Halve the refresh rate
+    /// display.set_display_clock(0xF, 1).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().display_clock, (0xF, 1));
+    /// ```
+    pub fn set_display_clock(&mut self, fosc: u8, divide: u8) -> Result<(), Error<CommE, PinE>> {
+        let (fosc, divide) = (fosc & 0xF, divide & 0xF);
+
+        Command::DisplayClockDiv(fosc, divide).send(&mut self.spi, &mut self.dc)?;
+        self.config.display_clock = (fosc, divide);
+
+        Ok(())
+    }
+
+    /// Set the gray scale table used to convert each channel's value to a pixel drive pulse width
+    ///
+    /// The controller maps each colour channel to one of 32 gray levels, each with its own pulse
+    /// width from 0 to 125 display clocks. Pulse widths set the gamma curve: increasing the
+    /// widths of the lower levels more slowly than the upper ones stops dark detail in photos
+    /// being crushed. Widths should increase from the first entry to the last. Only the low 7 bits
+    /// of each entry are used.
+    ///
+    /// The table is stored in [`Config::gamma`](./struct.Config.html#structfield.gamma) and
+    /// reapplied by later calls to [`init`](#method.init). Use
+    /// [`reset_gamma`](#method.reset_gamma) to go back to the built in linear table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Approximate a gamma of 2.2, from 1 to 125 clocks
+    /// let mut table = [0u8; 32];
+    ///
+    /// for (n, width) in table.iter_mut().enumerate() {
+    ///     let level = (n + 1) as f32 / 32.0;
+    ///
+    ///     *width = 1 + (124.0 * level.powf(2.2)) as u8;
+    /// }
+    ///
+    /// display.set_gamma(&table).unwrap();
+    /// ```
+    pub fn set_gamma(&mut self, table: &[u8; 32]) -> Result<(), Error<CommE, PinE>> {
+        Command::GrayScaleTable(*table).send(&mut self.spi, &mut self.dc)?;
+        self.config.gamma = Some(*table);
+
+        Ok(())
+    }
+
+    /// Reset the gray scale table to the controller's built in linear table
+    ///
+    /// This undoes [`set_gamma`](#method.set_gamma).
+    pub fn reset_gamma(&mut self) -> Result<(), Error<CommE, PinE>> {
+        Command::LinearGrayScale.send(&mut self.spi, &mut self.dc)?;
+        self.config.gamma = None;
+
+        Ok(())
+    }
+
+    /// Set the contrast of the red, green and blue channels, from 0 to 255
+    ///
+    /// Higher numbers mean higher contrast. This is the contrast at full
+    /// [brightness](#method.set_brightness); at lower brightness levels each channel is scaled
+    /// down by the same factor, so the balance between them is kept. The contrast is kept and
+    /// reapplied by later calls to [`init`](#method.init).
+    ///
+    /// Only commands are sent, so the framebuffer is untouched and no [`flush`](#method.flush) is
+    /// needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Boost contrast outdoors
+    /// display.set_contrast(0xff, 0xb0, 0xe0).unwrap();
+    ///
+    /// assert_eq!(display.config_snapshot().contrast, (0xff, 0xb0, 0xe0));
+    /// ```
+    pub fn set_contrast(&mut self, r: u8, g: u8, b: u8) -> Result<(), Error<CommE, PinE>> {
+        self.config.contrast = (r, g, b);
+
+        self.send_brightness()
+    }
+
+    /// Send the master current and scaled contrast for the configured brightness
+    fn send_brightness(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.send_brightness_level(self.config.brightness)
+    }
+
+    /// Send the master current and scaled contrast for a brightness level, without storing it
+    fn send_brightness_level(&mut self, level: u8) -> Result<(), Error<CommE, PinE>> {
+        let (r, g, b) = self.config.contrast;
+        let level = u32::from(level) + 1;
+
+        // Smallest master current step (out of 16) that covers `level` (out of 256)
+        let master = (level * 16).div_ceil(256) - 1;
+        let scale = |c: u8| (u32::from(c) * level * 16 / ((master + 1) * 256)) as u8;
+
+        Command::Contrast(scale(r), scale(g), scale(b)).send(&mut self.spi, &mut self.dc)?;
+        Command::MasterCurrent(master as u8).send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Get the current rotation of the display
+    pub fn rotation(&self) -> DisplayRotation {
+        self.config.rotation
+    }
+
+    /// Turn the display on (eg exiting sleep mode)
+    ///
+    /// The controller keeps its display memory while it is off, as long as its power supply stays
+    /// up, so the panel shows whatever was last sent without needing a new
+    /// [`flush`](#method.flush). Anything drawn into the framebuffer while the display was off is
+    /// only shown once it is flushed; use [`turn_on_restoring`](#method.turn_on_restoring) to send
+    /// it before the display comes back on.
+    pub fn turn_on(&mut self) -> Result<(), Error<CommE, PinE>> {
+        Command::DisplayOn(true).send(&mut self.spi, &mut self.dc)?;
+        self.config.display_on = true;
+
+        Ok(())
+    }
+
+    /// Send the framebuffer, then turn the display on
+    ///
+    /// Display memory survives [`turn_off`](#method.turn_off), but it only holds what was last
+    /// sent. If the framebuffer changed while the display was off, or a flush was interrupted, the
+    /// panel would briefly show the old or partial contents after [`turn_on`](#method.turn_on).
+    /// This sends the full framebuffer with [`flush`](#method.flush) while the display is still
+    /// off, so it comes back showing exactly the current framebuffer.
+    ///
+    /// If the controller lost power or was reset while off, use [`reinit`](#method.reinit)
+    /// instead, as its settings are lost too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    /// display.turn_off().unwrap();
+    ///
+    /// // Prepare the next screen while the display is asleep
+    /// display.set_pixel(10, 20, 0xffff);
+    ///
+    /// display.turn_on_restoring().unwrap();
+    /// ```
+    pub fn turn_on_restoring(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.flush()?;
+
+        self.turn_on()
+    }
+
+    /// Turn the display off (enter sleep mode)
+    ///
+    /// The panel stops being driven, but the controller keeps its display memory and settings.
+    pub fn turn_off(&mut self) -> Result<(), Error<CommE, PinE>> {
+        Command::DisplayOn(false).send(&mut self.spi, &mut self.dc)?;
+        self.config.display_on = false;
+
+        Ok(())
+    }
+
+    /// Turn the display on, fading in from black to the configured
+    /// [brightness](#method.set_brightness)
+    ///
+    /// The contrast is set to zero before the display is turned on, then the brightness is raised
+    /// in `steps` equal steps with a 10ms delay before each one. The fade takes `steps * 10`ms,
+    /// and sends `2 + 2 * steps` SPI writes. A `steps` of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init_off().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Fade in over 200ms
+    /// display.turn_on_faded(&mut delay, 20).unwrap();
+    /// ```
+    pub fn turn_on_faded<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        steps: u8,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        let steps = u32::from(steps.max(1));
+        let target = u32::from(self.config.brightness);
+
+        Command::Contrast(0, 0, 0).send(&mut self.spi, &mut self.dc)?;
+        self.turn_on()?;
+
+        for step in 1..=steps {
+            delay.delay_ms(FADE_STEP_MS);
+
+            self.send_brightness_level((target * step / steps) as u8)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fade the display out from the configured [brightness](#method.set_brightness) to black,
+    /// then turn it off
+    ///
+    /// The brightness is lowered in `steps` equal steps with a 10ms delay after each one, the last
+    /// step setting the contrast to zero. The display is then turned off and the configured
+    /// brightness is restored, so it is used again the next time the display is turned on. The
+    /// fade takes `steps * 10`ms, and sends `2 + 2 * steps` SPI writes. A `steps` of 0 is treated
+    /// as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Delay, Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+    /// // examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    /// let mut delay = Delay;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Fade out over 200ms
+    /// display.turn_off_faded(&mut delay, 20).unwrap();
+    /// ```
+    pub fn turn_off_faded<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        steps: u8,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        let steps = u32::from(steps.max(1));
+        let target = u32::from(self.config.brightness);
+
+        for step in (1..steps).rev() {
+            self.send_brightness_level((target * step / steps) as u8)?;
+
+            delay.delay_ms(FADE_STEP_MS);
+        }
+
+        Command::Contrast(0, 0, 0).send(&mut self.spi, &mut self.dc)?;
+        delay.delay_ms(FADE_STEP_MS);
+
+        self.turn_off()?;
+
+        self.send_brightness()
+    }
+
+    /// Instantly show every pixel as off, without changing display memory
+    ///
+    /// This uses the controller's "entire display off" mode, so it's a single command byte and
+    /// the image comes back as it was with [`unblank`](#method.unblank). This is useful for brief
+    /// privacy blanking. Compared to the other ways of making the display dark:
+    ///
+    /// * [`clear`](#method.clear) only changes the framebuffer. It needs a [`flush`](#method.flush)
+    ///   of the whole frame to take effect, and the previous content is lost.
+    /// * [`turn_off`](#method.turn_off) puts the controller to sleep, turning off the panel
+    ///   driving circuits to save power. Display memory is also preserved.
+    /// * `blank` keeps the controller running and the display memory intact, only changing how
+    ///   it is shown.
+    ///
+    /// Drawing and flushing still update display memory while the display is blanked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// display.blank().unwrap();
+    /// assert!(display.config_snapshot().blanked);
+    ///
+    /// display.unblank().unwrap();
+    /// assert!(!display.config_snapshot().blanked);
+    /// ```
+    pub fn blank(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.config.blanked = true;
+
+        self.send_display_mode()
+    }
+
+    /// Show display memory again after [`blank`](#method.blank)
+    ///
+    /// The display returns to normal or inverted mode, whichever was in use before blanking.
+    pub fn unblank(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.config.blanked = false;
+
+        self.send_display_mode()
+    }
+
+    /// Light every pixel at full brightness, or return to showing display memory
+    ///
+    /// This uses the controller's "entire display on" mode, which is useful for finding dead
+    /// pixels during factory testing. While it is enabled the panel ignores display memory, so
+    /// nothing drawn or flushed is visible until it is disabled again. Display memory and the
+    /// framebuffer are untouched, so the previous image reappears when it is disabled.
+    ///
+    /// This overrides [`blank`](#method.blank) and [`set_invert`](#method.set_invert) while it
+    /// is enabled. Disabling it returns to whichever of them was set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Check for dead pixels
+    /// display.set_all_on(true).unwrap();
+    /// // ...inspect the panel...
+    /// display.set_all_on(false).unwrap();
+    /// ```
+    pub fn set_all_on(&mut self, on: bool) -> Result<(), Error<CommE, PinE>> {
+        self.config.all_on = on;
+
+        self.send_display_mode()
+    }
+
+    /// Invert the colours shown on the display, or return to normal colours
+    ///
+    /// This is a single command byte using the controller's inverse display mode. Display memory
+    /// and the framebuffer are untouched, so it can be toggled at any time, even between flushes,
+    /// for example to flash the screen as an alarm. The setting is kept and reapplied by later
+    /// calls to [`init`](#method.init). While the display is [blanked](#method.blank) the new
+    /// setting is stored and takes effect on [`unblank`](#method.unblank).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Flash the screen
+    /// for _ in 0..3 {
+    ///     display.set_invert(true).unwrap();
+    ///     display.set_invert(false).unwrap();
+    /// }
+    ///
+    /// assert!(!display.config_snapshot().inverted);
+    /// ```
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), Error<CommE, PinE>> {
+        self.config.inverted = invert;
+
+        self.send_display_mode()
+    }
+
+    /// Draw raw RGB565 image data straight to the display
+    ///
+    /// `data` is in the same format used by `embedded-graphics`' `ImageRawBE` and `ImageRawLE`:
+    /// rows of `width` pixels, two bytes per pixel in the given `byte_order`. The image is drawn
+    /// with its top left corner at the logical (rotated) coordinate `top_left`. Parts of the image
+    /// past the right or bottom edge of the display are clipped.
+    ///
+    /// The image is streamed to the display without going through the framebuffer, so it's much
+    /// faster than drawing an `ImageRaw` pixel by pixel and flushing it. Big endian data in 65k
+    /// colour mode is sent exactly as given. Other formats are converted in small chunks as they
+    /// are sent. The framebuffer isn't changed, so the next [`flush`](#method.flush) will
+    /// overwrite the image with the framebuffer contents.
+    ///
+    /// With hardware rotation, or no rotation, the image is sent into a single draw area. With
+    /// [software rotation](#method.set_software_rotation) each row is sent into its own draw
+    /// area, which adds 6 command bytes per row.
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if
+    /// `width` is zero or `data` isn't a whole number of rows. Nothing is sent if `top_left` is
+    /// off screen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{ByteOrder, DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // The same data used to create an `ImageRawLE<Rgb565>` 86 pixels wide
+    /// let data = include_bytes!("../examples/ferris.raw");
+    ///
+    /// display
+    ///     .draw_image_raw(data, 86, ByteOrder::LittleEndian, (5, 10))
+    ///     .unwrap();
+    /// ```
+    pub fn draw_image_raw(
+        &mut self,
+        data: &[u8],
+        width: u8,
+        byte_order: ByteOrder,
+        top_left: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let row_len = usize::from(width) * 2;
+
+        if width == 0 || data.len() % row_len != 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (w, h) = self.dimensions();
+        let (x0, y0) = top_left;
+
+        if x0 >= w || y0 >= h || data.is_empty() {
+            return Ok(());
+        }
+
+        let height = data.len() / row_len;
+        let x1 = (usize::from(x0) + usize::from(width) - 1).min(usize::from(w) - 1) as u8;
+        let y1 = (usize::from(y0) + height - 1).min(usize::from(h) - 1) as u8;
+        let visible_len = usize::from(x1 - x0 + 1) * 2;
+        let rows = data.chunks_exact(row_len).take(usize::from(y1 - y0) + 1);
+
+        if self.config.software_rotation
+            && !matches!(self.config.rotation, DisplayRotation::Rotate0)
+        {
+            // Each logical row is a single controller row or column, but may run backwards
+            for (y, row) in (y0..=y1).zip(rows) {
+                let start = self.to_address((x0, y));
+                let end = self.to_address((x1, y));
+
+                self.set_draw_area(start.min(end), start.max(end))?;
+                self.write_image_pixels(&row[0..visible_len], byte_order, start > end)?;
+            }
+        } else {
+            let start = self.to_address((x0, y0));
+            let end = self.to_address((x1, y1));
+
+            self.set_draw_area(start, end)?;
+
+            if visible_len == row_len {
+                // Unclipped rows are contiguous so can be sent in one go
+                let len = usize::from(y1 - y0 + 1) * row_len;
+
+                self.write_image_pixels(&data[0..len], byte_order, false)?;
+            } else {
+                for row in rows {
+                    self.write_image_pixels(&row[0..visible_len], byte_order, false)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send RGB565 pixels in `byte_order` to the current draw area, optionally in reverse order,
+    /// converting them to the controller's format
+    fn write_image_pixels(
+        &mut self,
+        pixels: &[u8],
+        byte_order: ByteOrder,
+        reverse: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.window_full = false;
+
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(Error::Pin)?;
+
+        if !reverse
+            && byte_order == ByteOrder::BigEndian
+            && self.config.color_mode == ColorMode::CM65k
+        {
+            return write_chunked(&mut self.spi, pixels, self.max_transfer_size);
+        }
+
+        let mut chunk = [0u8; 64];
+        let mut len = 0;
+        let count = pixels.len() / 2;
+
+        for i in 0..count {
+            let n = if reverse { count - 1 - i } else { i };
+            let pixel = [pixels[n * 2], pixels[n * 2 + 1]];
+
+            let value = match byte_order {
+                ByteOrder::BigEndian => u16::from_be_bytes(pixel),
+                ByteOrder::LittleEndian => u16::from_le_bytes(pixel),
+            };
+
+            // The controller always expects big endian pixel data
+            match self.config.color_mode {
+                ColorMode::CM256 => {
+                    chunk[len] = rgb565_to_rgb332(value);
+                    len += 1;
+                }
+                ColorMode::CM65k => {
+                    chunk[len..len + 2].copy_from_slice(&value.to_be_bytes());
+                    len += 2;
+                }
+            }
+
+            if len == chunk.len() {
+                write_chunked(&mut self.spi, &chunk, self.max_transfer_size)?;
+                len = 0;
+            }
+        }
+
+        // Some HALs reject empty transfers, so only send a partly filled last chunk
+        if len > 0 {
+            write_chunked(&mut self.spi, &chunk[0..len], self.max_transfer_size)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draw a line using the controller's accelerated line drawing
+    ///
+    /// The controller draws the line between `start` and `end`, inclusive, in the RGB565 colour
+    /// `color`, so only 8 command bytes are sent instead of any pixel data. Coordinates are
+    /// logical and take the display rotation into account in the same way as
+    /// [`set_pixel`](#method.set_pixel). An
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if either
+    /// end is off screen.
+    ///
+    /// The line is drawn directly into the display's memory, bypassing the framebuffer. The next
+    /// [`flush`](#method.flush) will overwrite it with the framebuffer contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // White diagonal across the display
+    /// display.draw_line_accel((0, 0), (95, 63), 0xffff).unwrap();
+    /// ```
+    pub fn draw_line_accel(
+        &mut self,
+        start: (u8, u8),
+        end: (u8, u8),
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+
+        if start.0 >= w || start.1 >= h || end.0 >= w || end.1 >= h {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.draw_line_unchecked(start, end, color)
+    }
+
+    /// Draw the outline of a rectangle using the controller's accelerated line drawing
+    ///
+    /// Four hardware lines are drawn between the inclusive corners `top_left` and `bottom_right`,
+    /// in the RGB565 colour `color`. Rectangles one pixel wide or tall are drawn as a single line.
+    /// An error is returned if `bottom_right` is off screen or above or left of `top_left`.
+    ///
+    /// Coordinates are logical and take the display rotation into account in the same way as
+    /// [`set_pixel`](#method.set_pixel); they are converted to the controller's address space
+    /// before sending.
+    ///
+    /// The rectangle is drawn directly into the display's memory, bypassing the framebuffer. The
+    /// next [`flush`](#method.flush) will overwrite it with the framebuffer contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Green frame around the whole display
+    /// display
+    ///     .draw_rect_border_accel((0, 0), (95, 63), 0x07e0)
+    ///     .unwrap();
+    /// ```
+    pub fn draw_rect_border_accel(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let ((x0, y0), (x1, y1)) = (top_left, bottom_right);
+        let (w, h) = self.dimensions();
+
+        if x1 >= w || y1 >= h || x0 > x1 || y0 > y1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        // Top and bottom edges
+        self.draw_line_unchecked((x0, y0), (x1, y0), color)?;
+
+        if y1 > y0 {
+            self.draw_line_unchecked((x0, y1), (x1, y1), color)?;
+        }
+
+        // Left and right edges, between the top and bottom edges
+        if y1 - y0 > 1 {
+            self.draw_line_unchecked((x0, y0 + 1), (x0, y1 - 1), color)?;
+
+            if x1 > x0 {
+                self.draw_line_unchecked((x1, y0 + 1), (x1, y1 - 1), color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draw a rectangle using the controller's accelerated rectangle drawing, optionally filled
+    ///
+    /// The controller draws the outline between the inclusive corners `top_left` and
+    /// `bottom_right` in the RGB565 colour `stroke`. If `fill` is given the inside is filled with
+    /// that colour, otherwise filling is turned off and only the outline is drawn. Coordinates are
+    /// logical, like [`set_pixel`](#method.set_pixel). An
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if
+    /// `bottom_right` is off screen or above or left of `top_left`.
+    ///
+    /// 13 command bytes are sent however large the rectangle is, which makes this a quick way to
+    /// clear a panel of the display. The controller takes up to around 3ms to fill the whole
+    /// display, so allow time for large fills to finish before sending more commands.
+    ///
+    /// The rectangle is drawn directly into the display's memory, bypassing the framebuffer. The
+    /// next [`flush`](#method.flush) will overwrite it with the framebuffer contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Clear the score panel to black with a white border
+    /// display
+    ///     .draw_rect_accel((0, 0), (40, 11), 0xffff, Some(0x0000))
+    ///     .unwrap();
+    /// ```
+    pub fn draw_rect_accel(
+        &mut self,
+        top_left: (u8, u8),
+        bottom_right: (u8, u8),
+        stroke: u16,
+        fill: Option<u16>,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let ((x0, y0), (x1, y1)) = (top_left, bottom_right);
+        let (w, h) = self.dimensions();
+
+        if x1 >= w || y1 >= h || x0 > x1 || y0 > y1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (c1, r1) = self.to_address(top_left);
+        let (c2, r2) = self.to_address(bottom_right);
+
+        Command::FillMode(fill.is_some()).send(&mut self.spi, &mut self.dc)?;
+        Command::DrawRect(
+            c1.min(c2),
+            r1.min(r2),
+            c1.max(c2),
+            r1.max(r2),
+            stroke,
+            fill.unwrap_or(0),
+        )
+        .send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Copy a rectangular region of the display to another position using the controller's copy
+    /// window command
+    ///
+    /// The region between the inclusive corners `src.0` and `src.1` is copied so its top left
+    /// corner is at `dst`. Only 7 command bytes are sent however large the region is, so this
+    /// is a cheap way to scroll part of the display, for example moving a ticker left by a few
+    /// pixels each frame and drawing only the newly exposed strip. Coordinates are logical, like
+    /// [`set_pixel`](#method.set_pixel).
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and
+    /// nothing is sent if the source is above or left of its top left corner, or if either region
+    /// extends past the edge of the display.
+    ///
+    /// Like the other accelerated drawing methods this works on the display's memory, bypassing
+    /// the framebuffer, which isn't updated to match. The next [`flush`](#method.flush) will
+    /// overwrite the copy with the framebuffer contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Scroll a ticker along the top 8 rows left by 2 pixels
+    /// display.copy_window(((2, 0), (95, 7)), (0, 0)).unwrap();
+    /// ```
+    pub fn copy_window(
+        &mut self,
+        src: ((u8, u8), (u8, u8)),
+        dst: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let ((x0, y0), (x1, y1)) = src;
+        let (w, h) = self.dimensions();
+
+        if x1 >= w || y1 >= h || x0 > x1 || y0 > y1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let dst_end = (
+            u16::from(dst.0) + u16::from(x1 - x0),
+            u16::from(dst.1) + u16::from(y1 - y0),
+        );
+
+        if dst_end.0 >= u16::from(w) || dst_end.1 >= u16::from(h) {
+            return Err(Error::InvalidArgument);
+        }
+
+        // Rotation can swap or flip the corners, so use the top left in the controller's address
+        // space for both regions
+        let (c1, r1) = self.to_address((x0, y0));
+        let (c2, r2) = self.to_address((x1, y1));
+        let (c3, r3) = self.to_address(dst);
+        let (c4, r4) = self.to_address((dst_end.0 as u8, dst_end.1 as u8));
+
+        Command::CopyWindow(
+            c1.min(c2),
+            r1.min(r2),
+            c1.max(c2),
+            r1.max(r2),
+            c3.min(c4),
+            r3.min(r4),
+        )
+        .send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Clear a rectangular region of the display to black using the controller's clear window
+    /// command
+    ///
+    /// Everything between the inclusive corners `start` and `end` is cleared by the controller, so
+    /// only 5 command bytes are sent however large the region is. Coordinates are logical, like
+    /// [`set_pixel`](#method.set_pixel). An
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if `end` is
+    /// off screen or above or left of `start`.
+    ///
+    /// Only the display's memory is cleared; the framebuffer still holds the old contents, and the
+    /// next [`flush`](#method.flush) will restore them. Clear the same region in the framebuffer as
+    /// well if it should stay clear.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Wipe the status area along the bottom of the display
+    /// display.clear_window((0, 56), (95, 63)).unwrap();
+    /// ```
+    pub fn clear_window(
+        &mut self,
+        start: (u8, u8),
+        end: (u8, u8),
+    ) -> Result<(), Error<CommE, PinE>> {
+        let ((x0, y0), (x1, y1)) = (start, end);
+        let (w, h) = self.dimensions();
+
+        if x1 >= w || y1 >= h || x0 > x1 || y0 > y1 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let (c1, r1) = self.to_address(start);
+        let (c2, r2) = self.to_address(end);
+
+        Command::ClearWindow(c1.min(c2), r1.min(r2), c1.max(c2), r1.max(r2))
+            .send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Draw one spoke of a loading spinner using the display's accelerated line drawing
+    ///
+    /// A line of length `radius` is drawn from `center` at `angle_deg` degrees, where 0 points
+    /// right and angles increase clockwise. The end point is calculated with an integer sine table,
+    /// so this is cheap enough to call in a busy loop while waiting for something else. Parts of
+    /// the line past the edge of the display are clamped to the edge.
+    ///
+    /// The line is drawn directly by the controller, so the framebuffer isn't changed and the
+    /// previous spoke is not erased. Callers should clear it, for example by drawing it again in
+    /// the background colour, or redraw the background with [`flush`](#method.flush) before
+    /// drawing the next one.
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if
+    /// `center` is off screen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// for step in 0..36 {
+    ///     let angle = step * 10;
+    ///
+    ///     // Erase the previous spoke, then draw the next one in white
+    ///     display
+    ///         .draw_spinner_accel((48, 32), 10, angle + 350, 0x0000)
+    ///         .unwrap();
+    ///     display
+    ///         .draw_spinner_accel((48, 32), 10, angle, 0xffff)
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn draw_spinner_accel(
+        &mut self,
+        center: (u8, u8),
+        radius: u8,
+        angle_deg: u16,
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+
+        if center.0 >= w || center.1 >= h {
+            return Err(Error::InvalidArgument);
+        }
+
+        // Round the scaled offsets to the nearest pixel
+        let offset = |sin: i32| {
+            let scaled = i32::from(radius) * sin;
+
+            (scaled + 127 * scaled.signum()) / 255
+        };
+
+        let x = i32::from(center.0) + offset(sin_deg(angle_deg.wrapping_add(90)));
+        let y = i32::from(center.1) + offset(sin_deg(angle_deg));
+
+        let end = (
+            x.clamp(0, i32::from(w) - 1) as u8,
+            y.clamp(0, i32::from(h) - 1) as u8,
+        );
+
+        self.draw_line_unchecked(center, end, color)
+    }
+
+    /// Draw a crosshair through a point using the controller's accelerated line drawing
+    ///
+    /// One horizontal and one vertical line are drawn across the whole display through `at`, in
+    /// the RGB565 colour `color`. Coordinates are logical, like [`set_pixel`](#method.set_pixel).
+    /// If `at` is past the edge of the display it is clamped to the edge, so the crosshair stays
+    /// visible.
+    ///
+    /// Like the other accelerated drawing methods this draws directly into the display's memory,
+    /// bypassing the framebuffer. Moving the crosshair doesn't erase the old one; redraw it in the
+    /// background colour or [`flush`](#method.flush) the framebuffer first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Yellow measurement cursor
+    /// display.draw_crosshair_accel((40, 25), 0xffe0).unwrap();
+    /// ```
+    pub fn draw_crosshair_accel(
+        &mut self,
+        at: (u8, u8),
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+        let (x, y) = (at.0.min(w - 1), at.1.min(h - 1));
+
+        self.draw_line_unchecked((0, y), (w - 1, y), color)?;
+        self.draw_line_unchecked((x, 0), (x, h - 1), color)
+    }
+
+    /// Draw a filled circle using the controller's accelerated rectangle drawing
+    ///
+    /// The controller can only fill rectangles, so the circle is split into horizontal spans, one
+    /// per row, and each is drawn as a filled rectangle one pixel tall in the RGB565 colour
+    /// `color`. `center` is in logical coordinates, like [`set_pixel`](#method.set_pixel). A
+    /// `radius` of 0 draws a single pixel.
+    ///
+    /// Spans are clipped to the display, and rows past the top or bottom edge are skipped. Up to
+    /// `2 * radius + 1` rectangle commands of 11 bytes are sent, along with two commands turning
+    /// rectangle filling on and off again. Each rectangle only covers one row, so it finishes
+    /// drawing before the next command arrives.
+    ///
+    /// Like the other accelerated drawing methods this draws directly into the display's memory,
+    /// bypassing the framebuffer. The next [`flush`](#method.flush) will overwrite it with the
+    /// framebuffer contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Red hub for a gauge needle
+    /// display.fill_circle_accel((48, 32), 5, 0xf800).unwrap();
+    /// ```
+    pub fn fill_circle_accel(
+        &mut self,
+        center: (u8, u8),
+        radius: u8,
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (w, h) = self.dimensions();
+        let (cx, cy) = (i32::from(center.0), i32::from(center.1));
+        let r = i32::from(radius);
+
+        Command::FillMode(true).send(&mut self.spi, &mut self.dc)?;
+
+        for dy in -r..=r {
+            let y = cy + dy;
+
+            if y < 0 || y >= i32::from(h) {
+                continue;
+            }
+
+            let half = isqrt((r * r - dy * dy) as u32) as i32;
+            let x0 = (cx - half).max(0);
+            let x1 = (cx + half).min(i32::from(w) - 1);
+
+            if x0 > x1 {
+                continue;
+            }
+
+            let (c1, r1) = self.to_address((x0 as u8, y as u8));
+            let (c2, r2) = self.to_address((x1 as u8, y as u8));
+
+            Command::DrawRect(c1.min(c2), r1.min(r2), c1.max(c2), r1.max(r2), color, color)
+                .send(&mut self.spi, &mut self.dc)?;
+        }
+
+        Command::FillMode(false).send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Draw an accelerated line between two on-screen logical coordinates
+    fn draw_line_unchecked(
+        &mut self,
+        start: (u8, u8),
+        end: (u8, u8),
+        color: u16,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let (c1, r1) = self.to_address(start);
+        let (c2, r2) = self.to_address(end);
+
+        Command::DrawLine(c1, r1, c2, r2, color).send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Map on-screen logical coordinates to the controller's (column, row) address space
+    ///
+    /// With hardware rotation the controller remaps addresses itself, swapping rows and columns
+    /// for 90 and 270 degree rotations. With software rotation the controller is unrotated.
+    fn to_address(&self, (x, y): (u8, u8)) -> (u8, u8) {
+        if self.config.software_rotation {
+            let (x, y) = self.to_unrotated(u32::from(x), u32::from(y));
+
+            (x as u8, y as u8)
+        } else {
+            match self.config.rotation {
+                DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+                DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (y, x),
+            }
+        }
+    }
+
+    /// Pan the display to show GDDRAM starting at the given row
+    ///
+    /// This sets the display start line to `line` and resets the display offset to 0, so the top
+    /// row of the panel shows GDDRAM row `line`, the next row shows `line + 1`, and so on. Rows
+    /// wrap around, so after row 63 the display continues from row 0. Valid lines are 0-63; higher
+    /// values are wrapped into this range.
+    ///
+    /// Rows are those of the controller's GDDRAM, which runs along the short axis of the panel. For
+    /// 90 and 270 degree hardware rotations this pans horizontally.
+    ///
+    /// No pixel data is sent, so this is a cheap way to scroll through content already on the
+    /// display.
+    pub fn pan_to(&mut self, line: u8) -> Result<(), Error<CommE, PinE>> {
+        self.set_start_line(line)?;
+
+        Command::DisplayOffset(0).send(&mut self.spi, &mut self.dc)?;
+        self.config.display_offset = 0;
+
+        Ok(())
+    }
+
+    /// Set the display start line, the GDDRAM row shown at the top of the display
+    ///
+    /// Rows wrap around, so incrementing the start line scrolls the whole frame up by one row
+    /// without sending any pixel data, which suits terminal style log views. Only the low 6 bits
+    /// are used, so lines past 63 wrap back to 0. Unlike [`pan_to`](#method.pan_to), the
+    /// [display offset](#method.set_display_offset) is left as it is.
+    ///
+    /// Rows are those of the controller's GDDRAM, so for 90 and 270 degree hardware rotations this
+    /// scrolls horizontally. The line is stored in
+    /// [`Config::start_line`](./struct.Config.html#structfield.start_line) and reapplied by later
+    /// calls to [`init`](#method.init).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Scroll up by one text line of 8 rows
+    /// let line = display.config_snapshot().start_line + 8;
+    /// display.set_start_line(line).unwrap();
+    /// ```
+    pub fn set_start_line(&mut self, line: u8) -> Result<(), Error<CommE, PinE>> {
+        let line = line & 0x3F;
+
+        Command::StartLine(line).send(&mut self.spi, &mut self.dc)?;
+        self.config.start_line = line;
+
+        Ok(())
+    }
+
+    /// Set the multiplex ratio, one less than the number of GDDRAM rows the controller drives
+    ///
+    /// The default of 63 drives all 64 rows. Panels with fewer physical rows, like a 96x36 module,
+    /// need a smaller ratio such as 35, often combined with
+    /// [`set_display_offset`](#method.set_display_offset) to line the driven rows up with the
+    /// panel. Valid ratios are 15-63; an
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and nothing
+    /// is sent for any other value.
+    ///
+    /// The ratio is stored in [`Config::multiplex`](./struct.Config.html#structfield.multiplex),
+    /// reapplied by later calls to [`init`](#method.init), and used by
+    /// [`clear_active`](#method.clear_active).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Drive the 36 rows of a cut down panel
+    /// display.set_multiplex(35).unwrap();
+    ///
+    /// assert!(display.set_multiplex(14).is_err());
+    /// ```
+    pub fn set_multiplex(&mut self, ratio: u8) -> Result<(), Error<CommE, PinE>> {
+        if !(15..DISPLAY_HEIGHT).contains(&ratio) {
+            return Err(Error::InvalidArgument);
+        }
+
+        Command::Multiplex(ratio).send(&mut self.spi, &mut self.dc)?;
+        self.config.multiplex = ratio;
+
+        Ok(())
+    }
+
+    /// Set the display offset, the number of rows the display is shifted up by
+    ///
+    /// With an offset of `offset`, the top row of the panel shows GDDRAM row `offset` (plus the
+    /// [start line](#method.set_start_line)), wrapping around after row 63. This is the usual fix
+    /// for modules whose image is shifted vertically and wraps around, because the panel's first
+    /// row isn't wired to the controller's first row. Valid offsets are 0-63; an
+    /// [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and nothing
+    /// is sent for any other value.
+    ///
+    /// The offset is stored in
+    /// [`Config::display_offset`](./struct.Config.html#structfield.display_offset) and reapplied
+    /// by later calls to [`init`](#method.init). [`pan_to`](#method.pan_to) resets it to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // This module's panel starts 16 rows into GDDRAM
+    /// display.set_display_offset(16).unwrap();
+    /// ```
+    pub fn set_display_offset(&mut self, offset: u8) -> Result<(), Error<CommE, PinE>> {
+        if offset >= DISPLAY_HEIGHT {
+            return Err(Error::InvalidArgument);
+        }
+
+        Command::DisplayOffset(offset).send(&mut self.spi, &mut self.dc)?;
+        self.config.display_offset = offset;
+
+        Ok(())
+    }
+
+    /// Start the controller scrolling part of the display continuously
+    ///
+    /// Every `interval` frames, the `num_rows` rows starting at `start_row` move `h_offset`
+    /// columns horizontally, and the whole display moves `v_offset` rows vertically. Either offset
+    /// can be 0 to scroll in one direction only. Scrolling wraps around the edges of the display
+    /// and carries on without any further commands, so it costs no CPU time, which makes it a good
+    /// fit for something like a now playing track name that scrolls for as long as it is shown.
+    ///
+    /// Columns and rows are those of the controller's GDDRAM, like
+    /// [`pan_to`](#method.pan_to), so for 90 and 270 degree hardware rotations horizontal
+    /// scrolling moves content vertically on the panel and `start_row` picks a band of columns.
+    ///
+    /// Any scroll already running is stopped first, as the controller requires. The controller
+    /// moves the display's memory itself, so the framebuffer no longer matches it. Don't flush or
+    /// draw with the accelerated methods while scrolling; call
+    /// [`stop_scroll`](#method.stop_scroll) first.
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned and
+    /// nothing is sent if `h_offset` is more than 95, `v_offset` is more than 63, or the band of
+    /// rows extends past the bottom of the display.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, NFrames, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// display.init().unwrap();
+    ///
+    /// // Scroll a title along the top 8 rows one column every 6 frames
+    /// display.start_scroll(1, 0, 8, 0, NFrames::F6).unwrap();
+    ///
+    /// // Later, stop scrolling and redraw
+    /// display.stop_scroll().unwrap();
+    /// display.flush().unwrap();
+    /// ```
+    pub fn start_scroll(
+        &mut self,
+        h_offset: u8,
+        start_row: u8,
+        num_rows: u8,
+        v_offset: u8,
+        interval: NFrames,
+    ) -> Result<(), Error<CommE, PinE>> {
+        if h_offset >= DISPLAY_WIDTH
+            || v_offset >= DISPLAY_HEIGHT
+            || u16::from(start_row) + u16::from(num_rows) > u16::from(DISPLAY_HEIGHT)
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        Command::DeactivateScroll.send(&mut self.spi, &mut self.dc)?;
+
+        Command::SetupScroll {
+            h_offset,
+            start_row,
+            num_rows,
+            v_offset,
+            interval,
+        }
+        .send(&mut self.spi, &mut self.dc)?;
+
+        Command::ActivateScroll.send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Stop any continuous scroll started with [`start_scroll`](#method.start_scroll)
+    ///
+    /// The display keeps showing the scrolled content where it stopped. Flush to show the
+    /// framebuffer again.
+    pub fn stop_scroll(&mut self) -> Result<(), Error<CommE, PinE>> {
+        Command::DeactivateScroll.send(&mut self.spi, &mut self.dc)
+    }
+
+    /// Get a snapshot of the controller settings tracked by the driver
+    ///
+    /// The SSD1331 is write-only over SPI, so this reflects what the driver believes it has set,
+    /// not the actual state of the chip. See [`Config`](./struct.Config.html) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ssd1331::test_helpers::{Spi, Pin};
+    /// use ssd1331::{DisplayRotation::Rotate0, Ssd1331};
+    ///
+    /// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+    /// let spi = Spi;
+    /// let dc = Pin;
+    ///
+    /// let mut display = Ssd1331::new(spi, dc, Rotate0);
+    /// assert!(!display.config_snapshot().display_on);
+    ///
+    /// display.init().unwrap();
+    ///
+    /// let config = display.config_snapshot();
+    ///
+    /// assert!(config.display_on);
+    /// assert_eq!(config.contrast, (0x91, 0x50, 0x7d));
+    /// ```
+    pub fn config_snapshot(&self) -> Config {
+        self.config
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions},
+    geometry::{Point, Size},
+    pixelcolor::{
+        raw::{RawData, RawU16},
+        Rgb565, RgbColor,
+    },
+    primitives::{PointsIter, Rectangle},
+    Drawable, Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<SPI, DC, B> DrawTarget for Ssd1331<SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8>,
+    DC: OutputPin,
+    B: FrameBuffer,
+{
+    type Color = Rgb565;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        // Runs of same coloured pixels next to each other on a row are written in one go. Shapes
+        // are mostly filled a row at a time, so this saves a lot of per pixel work.
+        let mut span: Option<(Point, u32, Rgb565)> = None;
+
+        for Pixel(pos, color) in pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+        {
+            match span {
+                Some((start, len, span_color))
+                    if span_color == color && pos.y == start.y && pos.x == start.x + len as i32 =>
+                {
+                    span = Some((start, len + 1, span_color));
+                }
+                _ => {
+                    if let Some((start, len, span_color)) = span {
+                        self.fill_span(
+                            start.x as u32,
+                            start.y as u32,
+                            len,
+                            RawU16::from(span_color).into_inner(),
+                        );
+                    }
+
+                    span = Some((pos, 1, color));
+                }
+            }
+        }
+
+        if let Some((start, len, color)) = span {
+            self.fill_span(
+                start.x as u32,
+                start.y as u32,
+                len,
+                RawU16::from(color).into_inner(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounding_box());
+
+        let bottom_right = match drawable.bottom_right() {
+            Some(bottom_right) => bottom_right,
+            None => return Ok(()),
+        };
+
+        // Colours are given for the whole area, so skip those for pixels off screen
+        for (pos, color) in area.points().zip(colors) {
+            if drawable.contains(pos) {
+                if let Some(idx) = self.pixel_index(pos.x as u32, pos.y as u32) {
+                    self.write_pixel(idx, RawU16::from(color).into_inner());
+                }
+            }
+        }
+
+        let top_left = drawable.top_left;
+
+        self.mark_dirty(
+            (top_left.x as u8, top_left.y as u8),
+            (bottom_right.x as u8, bottom_right.y as u8),
+        );
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let value = RawU16::from(color).into_inner();
+
+        if let Some(bottom_right) = area.bottom_right() {
+            for y in area.top_left.y..=bottom_right.y {
+                self.fill_span(area.top_left.x as u32, y as u32, area.size.width, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_color(RawU16::from(color).into_inner());
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<SPI, DC, B> OriginDimensions for Ssd1331<SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8>,
+    DC: OutputPin,
+    B: FrameBuffer,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{
+        CaptureSpi, CountingSpi, Delay, Pin, RecordingPin, RecordingSpi, Spi,
+    };
+    use core::cell::Cell;
+
+    /// Column and row address commands sent before the pixel data in a full `flush()`
+    const FLUSH_HEADER_LEN: usize = 6;
+
+    /// Read a pixel back out of the framebuffer
+    fn pixel<SPI, DC, B>(display: &Ssd1331<SPI, DC, B>, x: u32, y: u32) -> u16
+    where
+        SPI: hal::blocking::spi::Write<u8>,
+        DC: OutputPin,
+        B: FrameBuffer,
+    {
+        display.get_pixel(x, y).unwrap()
+    }
+
+    /// Maps a logical `(x, y)` coordinate to its `(segment, common)` position on the panel
+    type PanelMap = fn(u32, u32) -> (u32, u32);
+
+    /// Where the `n`th pixel of a full frame lands on the panel, as `(segment, common)`, for the
+    /// given remap byte
+    fn panel_position(remap: u8, n: usize) -> (u32, u32) {
+        let (w, h) = (DISPLAY_WIDTH as u32, DISPLAY_HEIGHT as u32);
+        let n = n as u32;
+
+        // Bit 0: vertical address increment
+        let (col, row) = if remap & 0x01 != 0 {
+            (n / h, n % h)
+        } else {
+            (n % w, n / w)
+        };
+
+        address_position(remap, col, row)
+    }
+
+    /// Where a controller `(column, row)` address lands on the panel, as `(segment, common)`, for
+    /// the given remap byte
+    fn address_position(remap: u8, col: u32, row: u32) -> (u32, u32) {
+        let (w, h) = (DISPLAY_WIDTH as u32, DISPLAY_HEIGHT as u32);
+
+        // Bit 1: column remap, bit 4: COM scan remap
+        let seg = if remap & 0x02 != 0 { w - 1 - col } else { col };
+        let com = if remap & 0x10 != 0 { h - 1 - row } else { row };
+
+        (seg, com)
+    }
+
+    #[test]
+    fn flush_byte_order_matches_rotation() {
+        let cases: [(DisplayRotation, u8, PanelMap); 4] = [
+            (DisplayRotation::Rotate0, 0x60, |x, y| (x, y)),
+            (DisplayRotation::Rotate90, 0x63, |x, y| (95 - y, x)),
+            (DisplayRotation::Rotate180, 0x72, |x, y| (95 - x, 63 - y)),
+            (DisplayRotation::Rotate270, 0x71, |x, y| (y, 63 - x)),
+        ];
+
+        for (rotation, remap, expected) in cases.iter() {
+            let mut out = [0u8; FLUSH_HEADER_LEN + BUF_SIZE];
+            let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, *rotation);
+            let (w, h) = display.dimensions();
+            let (w, h) = (u32::from(w), u32::from(h));
+
+            let points = [(0, 0), (w - 1, 0), (0, h - 1), (w - 1, h - 1), (5, 9)];
+
+            for (i, (x, y)) in points.iter().enumerate() {
+                display.set_pixel(*x, *y, i as u16 + 1);
+            }
+
+            display.flush().unwrap();
+
+            let (spi, _dc) = display.release();
+            let data = &spi.written()[FLUSH_HEADER_LEN..];
+
+            for (i, (x, y)) in points.iter().enumerate() {
+                let value = i as u16 + 1;
+                let n = data
+                    .chunks(2)
+                    .position(|p| p == value.to_be_bytes())
+                    .unwrap();
+
+                assert_eq!(
+                    panel_position(*remap, n),
+                    expected(*x, *y),
+                    "{:?} ({}, {})",
+                    rotation,
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn accel_matches_framebuffer_rotation() {
+        fn rotated(
+            spi: CaptureSpi<'_>,
+            rotation: DisplayRotation,
+            software: bool,
+        ) -> Ssd1331<CaptureSpi<'_>, Pin> {
+            let mut display = Ssd1331::new(spi, Pin, rotation);
+
+            if software {
+                display.set_software_rotation(rotation);
+            }
+
+            display
+        }
+
+        let cases = [
+            (DisplayRotation::Rotate0, 0x60),
+            (DisplayRotation::Rotate90, 0x63),
+            (DisplayRotation::Rotate180, 0x72),
+            (DisplayRotation::Rotate270, 0x71),
+        ];
+
+        for (rotation, hardware_remap) in cases.iter() {
+            for software in [false, true].iter() {
+                // Software rotation leaves the controller unrotated
+                let remap = if *software { 0x60 } else { *hardware_remap };
+                let (w, h) = rotated(CaptureSpi::new(&mut []), *rotation, *software).dimensions();
+                let points = [(0, 0), (w - 1, 0), (0, h - 1), (w - 1, h - 1), (5, 9)];
+
+                for (x, y) in points.iter() {
+                    let mut line = [0u8; 8];
+                    let mut accel = rotated(CaptureSpi::new(&mut line), *rotation, *software);
+
+                    accel
+                        .draw_rect_border_accel((*x, *y), (*x, *y), 0xffff)
+                        .unwrap();
+
+                    let mut out = [0u8; FLUSH_HEADER_LEN + BUF_SIZE];
+                    let mut display = rotated(CaptureSpi::new(&mut out), *rotation, *software);
+
+                    display.set_pixel(u32::from(*x), u32::from(*y), 0xffff);
+                    display.flush().unwrap();
+
+                    let (accel_spi, _dc) = accel.release();
+                    let (spi, _dc) = display.release();
+                    let (col, row) = (accel_spi.written()[1], accel_spi.written()[2]);
+                    let n = spi.written()[FLUSH_HEADER_LEN..]
+                        .chunks(2)
+                        .position(|p| p == [0xff, 0xff])
+                        .unwrap();
+
+                    assert_eq!(
+                        accel_spi.written(),
+                        [0x21, col, row, col, row, 0x3e, 0x3f, 0x3e]
+                    );
+                    assert_eq!(
+                        address_position(remap, col.into(), row.into()),
+                        panel_position(remap, n),
+                        "{:?} software: {} ({}, {})",
+                        rotation,
+                        software,
+                        x,
+                        y
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn record_flush_matches_flush() {
+        let dc_state = Cell::new(false);
+        let mut log = [(false, 0u8); FLUSH_HEADER_LEN + BUF_SIZE];
+        let mut display = Ssd1331::new(
+            RecordingSpi::new(&mut log, &dc_state),
+            RecordingPin::new(&dc_state),
+            DisplayRotation::Rotate90,
+        );
+
+        // Recorded pixel data is only in the controller's format for a big endian framebuffer
+        display.byte_order = ByteOrder::BigEndian;
+        display.set_pixel(3, 90, 0x1234);
+        display.set_pixel(63, 95, 0xffff);
+
+        let mut ops = [Op::Command(&[]); 3];
+        let len = display.record_flush(&mut ops);
+        let recorded = ops[0..len]
+            .iter()
+            .flat_map(|op| op.bytes().iter().map(move |b| (!op.is_command(), *b)));
+
+        // Copy the framebuffer, as the ops borrow it while flushing needs it mutably
+        let mut expected = [(false, 0u8); FLUSH_HEADER_LEN + BUF_SIZE];
+
+        for (e, r) in expected.iter_mut().zip(recorded) {
+            *e = r;
+        }
+
+        display.flush().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(len, 2);
+        assert_eq!(spi.log(), &expected[..]);
+    }
+
+    #[test]
+    fn shift_pixels_moves_pattern() {
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90].iter() {
+            let mut display = Ssd1331::new(Spi, Pin, *rotation);
+            let (w, h) = display.dimensions();
+            let (w, h) = (u32::from(w), u32::from(h));
+
+            // A filled row along the top and column along the right edge, plus one pixel inside
+            for x in 0..w {
+                display.set_pixel(x, 0, 0x1111);
+            }
+            for y in 0..h {
+                display.set_pixel(w - 1, y, 0x2222);
+            }
+            display.set_pixel(5, 9, 0x3333);
+
+            display.shift_pixels(-2, 3);
+
+            assert_eq!(pixel(&display, 3, 12), 0x3333, "{:?}", rotation);
+            assert_eq!(pixel(&display, 5, 9), 0, "{:?}", rotation);
+            assert_eq!(pixel(&display, 0, 3), 0x1111, "{:?}", rotation);
+            assert_eq!(pixel(&display, w - 3, 3), 0x2222, "{:?}", rotation);
+            assert_eq!(pixel(&display, w - 3, h - 1), 0x2222, "{:?}", rotation);
+            // Exposed edges are black
+            assert_eq!(pixel(&display, 0, 2), 0, "{:?}", rotation);
+            assert_eq!(pixel(&display, w - 1, 3), 0, "{:?}", rotation);
+            assert_eq!(pixel(&display, w - 2, 3), 0, "{:?}", rotation);
+
+            display.shift_pixels(i8::MAX, 0);
+
+            assert!(display.buffer.iter().all(|b| *b == 0), "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn antiburn_tick_cycles() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+
+        display.set_pixel(10, 20, 0xffff);
+
+        let expected = [(11, 20), (11, 21), (10, 21), (10, 20)];
+
+        for (x, y) in expected.iter() {
+            display.antiburn_tick();
+
+            assert_eq!(pixel(&display, *x, *y), 0xffff);
+            assert_eq!(display.buffer.iter().filter(|b| **b != 0).count(), 2);
+        }
+    }
+
+    /// Simulate the controller receiving a recorded byte stream, writing the RGB565 value of each
+    /// pixel to `panel`, indexed by `common * 96 + segment`
+    ///
+    /// Only the column and row address commands are supported; the remap byte is given.
+    fn simulate(remap: u8, log: &[(bool, u8)], panel: &mut [u16]) {
+        let (mut c0, mut c1, mut r0, mut r1) = (0, 95, 0, 63);
+        let (mut c, mut r) = (0, 0);
+        let mut i = 0;
+
+        while i < log.len() {
+            let (dc, byte) = log[i];
+            let next = (
+                u32::from(log[i + 1].1),
+                log.get(i + 2).map(|(_, b)| u32::from(*b)),
+            );
+
+            if dc {
+                let (seg, com) = address_position(remap, c, r);
+
+                panel[(com * 96 + seg) as usize] = u16::from_be_bytes([byte, next.0 as u8]);
+
+                // Bit 0: vertical address increment
+                if remap & 0x01 != 0 {
+                    r += 1;
+
+                    if r > r1 {
+                        r = r0;
+                        c = if c == c1 { c0 } else { c + 1 };
+                    }
+                } else {
+                    c += 1;
+
+                    if c > c1 {
+                        c = c0;
+                        r = if r == r1 { r0 } else { r + 1 };
+                    }
+                }
+
+                i += 2;
+            } else {
+                let (start, end) = (next.0, next.1.unwrap());
+
+                match byte {
+                    0x15 => {
+                        c0 = start;
+                        c1 = end;
+                        c = start;
+                    }
+                    0x75 => {
+                        r0 = start;
+                        r1 = end;
+                        r = start;
+                    }
+                    other => panic!("unexpected command {:#x}", other),
+                }
+
+                i += 3;
+            }
+        }
+    }
+
+    #[test]
+    fn draw_image_raw_matches_framebuffer() {
+        fn recording<'a>(
+            log: &'a mut [(bool, u8)],
+            dc_state: &'a Cell<bool>,
+            rotation: DisplayRotation,
+            software: bool,
+        ) -> Ssd1331<RecordingSpi<'a>, RecordingPin<'a>> {
+            let mut display = Ssd1331::new(
+                RecordingSpi::new(log, dc_state),
+                RecordingPin::new(dc_state),
+                rotation,
+            );
+
+            if software {
+                display.set_software_rotation(rotation);
+            }
+
+            display.byte_order = ByteOrder::BigEndian;
+
+            display
+        }
+
+        let cases = [
+            (DisplayRotation::Rotate0, 0x60),
+            (DisplayRotation::Rotate90, 0x63),
+            (DisplayRotation::Rotate180, 0x72),
+            (DisplayRotation::Rotate270, 0x71),
+        ];
+
+        // 4x3 image, with every pixel a different colour
+        let mut image = [0u8; 4 * 3 * 2];
+
+        for (n, pixel) in image.chunks_exact_mut(2).enumerate() {
+            pixel.copy_from_slice(&(0x1000 + n as u16).to_le_bytes());
+        }
+
+        for (rotation, hardware_remap) in cases.iter() {
+            for software in [false, true].iter() {
+                let remap = if *software { 0x60 } else { *hardware_remap };
+                let dc_state = Cell::new(false);
+                let mut image_log = [(false, 0u8); 256];
+                let mut flush_log = [(false, 0u8); FLUSH_HEADER_LEN + BUF_SIZE];
+                let mut direct = recording(&mut image_log, &dc_state, *rotation, *software);
+                let mut buffered = recording(&mut flush_log, &dc_state, *rotation, *software);
+
+                let (w, h) = direct.dimensions();
+
+                // One unclipped copy, and one clipped by the bottom right corner
+                for (x0, y0) in [(5, 9), (w - 3, h - 2)].iter() {
+                    direct
+                        .draw_image_raw(&image, 4, ByteOrder::LittleEndian, (*x0, *y0))
+                        .unwrap();
+
+                    for (n, pixel) in image.chunks_exact(2).enumerate() {
+                        let x = u32::from(*x0) + n as u32 % 4;
+                        let y = u32::from(*y0) + n as u32 / 4;
+
+                        if x < u32::from(w) && y < u32::from(h) {
+                            buffered.set_pixel(x, y, u16::from_le_bytes([pixel[0], pixel[1]]));
+                        }
+                    }
+                }
+
+                buffered.flush().unwrap();
+
+                let (direct_spi, _dc) = direct.release();
+                let (buffered_spi, _dc) = buffered.release();
+                let mut drawn = [0u16; 96 * 64];
+                let mut flushed = [0u16; 96 * 64];
+
+                simulate(remap, direct_spi.log(), &mut drawn);
+                simulate(remap, buffered_spi.log(), &mut flushed);
+
+                assert!(
+                    drawn.iter().eq(flushed.iter()),
+                    "{:?} software: {}",
+                    rotation,
+                    software
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn draw_image_raw_skips_empty_write() {
+        // 32 pixels in 65k colour mode exactly fill one 64 byte chunk
+        let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+        display
+            .draw_image_raw(&[0; 64], 32, ByteOrder::LittleEndian, (0, 0))
+            .unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!((spi.writes, spi.bytes), (2, 6 + 64));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "framebuffer is 16 bytes"))]
+    fn flush_rejects_short_buffer() {
+        let mut buffer = [0u8; 16];
+        let mut display = Ssd1331::from_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer[..]);
+
+        // Pixels past the end of the buffer are ignored
+        display.set_pixel(95, 63, 0xffff);
+
+        assert!(matches!(display.flush(), Err(Error::InvalidArgument)));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "framebuffer is 16 bytes"))]
+    fn flush_dirty_rejects_short_buffer() {
+        let mut buffer = [0u8; 16];
+        let mut display = Ssd1331::from_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer[..]);
+
+        assert!(matches!(display.flush_dirty(), Err(Error::InvalidArgument)));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "framebuffer is 16 bytes"))]
+    fn flush_rows_rejects_short_buffer() {
+        let mut buffer = [0u8; 16];
+        let mut display = Ssd1331::from_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer[..]);
+
+        assert!(matches!(
+            display.flush_rows(0, 1),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "framebuffer is 16 bytes"))]
+    fn flush_yielding_rejects_short_buffer() {
+        let mut buffer = [0u8; 16];
+        let mut display = Ssd1331::from_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer[..]);
+
+        assert!(matches!(
+            display.flush_yielding(|| ()),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "framebuffer is 16 bytes"))]
+    fn record_flush_rejects_short_buffer() {
+        let mut buffer = [0u8; 16];
+        let display = Ssd1331::from_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer[..]);
+        let mut ops = [Op::Command(&[]); 2];
+
+        assert_eq!(display.record_flush(&mut ops), 0);
+    }
+
+    #[test]
+    fn clear_color_fills_buffer() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate90);
+
+        display.byte_order = ByteOrder::BigEndian;
+        display.dirty = None;
+        display.clear_color(0x1234);
+
+        assert!(display
+            .buffer_mut()
+            .chunks_exact(2)
+            .all(|p| p == [0x12, 0x34]));
+        assert!(display.needs_flush());
+
+        let mut display = Ssd1331::new_256(Spi, Pin, DisplayRotation::Rotate0);
+
+        display.clear_color(0xf800);
+
+        assert!(display.buffer_mut().iter().all(|p| *p == 0xe0));
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn fill_matches_draw_iter() {
+        // Partly off the right and bottom of every rotation
+        let area = Rectangle::new(Point::new(-3, 50), Size::new(70, 50));
+        let color = |p: Point| Rgb565::new(p.x as u8, p.y as u8, (p.x + p.y) as u8);
+
+        for software in [false, true].iter() {
+            for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90].iter() {
+                let new = || {
+                    let mut display = Ssd1331::new(Spi, Pin, *rotation);
+
+                    if *software {
+                        display.set_software_rotation(*rotation);
+                    }
+
+                    display.dirty = None;
+                    display
+                };
+
+                let mut solid = new();
+                let mut contiguous = new();
+                let mut expected_solid = new();
+                let mut expected_contiguous = new();
+
+                solid.fill_solid(&area, Rgb565::CYAN).unwrap();
+                contiguous
+                    .fill_contiguous(&area, area.points().map(color))
+                    .unwrap();
+                expected_solid
+                    .draw_iter(area.points().map(|p| Pixel(p, Rgb565::CYAN)))
+                    .unwrap();
+                expected_contiguous
+                    .draw_iter(area.points().map(|p| Pixel(p, color(p))))
+                    .unwrap();
+
+                assert!(solid.buffer_mut() == expected_solid.buffer_mut());
+                assert!(contiguous.buffer_mut() == expected_contiguous.buffer_mut());
+                assert_eq!(solid.dirty, expected_solid.dirty);
+                assert_eq!(contiguous.dirty, expected_contiguous.dirty);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn draw_target_clear_fills_buffer() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+
+        DrawTarget::clear(&mut display, Rgb565::GREEN).unwrap();
+
+        assert!((0..96).all(|x| (0..64).all(|y| pixel(&display, x, y) == 0x07e0)));
+    }
+
+    #[test]
+    fn with_buffer_keeps_contents() {
+        let mut buffer = [0u8; BUF_SIZE];
+
+        let mut display = Ssd1331::with_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer);
+        display.byte_order = ByteOrder::BigEndian;
+        display.set_pixel(1, 0, 0xf800);
+        display.release();
+
+        assert_eq!(buffer[2..4], [0xf8, 0x00]);
+
+        // A later display sharing the buffer starts with what was drawn
+        let mut display = Ssd1331::with_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer);
+        display.byte_order = ByteOrder::BigEndian;
+
+        assert_eq!(display.dirty, Some(((0, 0), (95, 63))));
+        assert_eq!(pixel(&display, 1, 0), 0xf800);
+    }
+
+    #[test]
+    #[should_panic(expected = "framebuffer is 12287 bytes, at least 12288 are needed")]
+    fn with_buffer_rejects_short_slice() {
+        let mut buffer = [0u8; BUF_SIZE - 1];
+
+        Ssd1331::with_buffer(Spi, Pin, DisplayRotation::Rotate0, &mut buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "framebuffer is 100 bytes, at least 6144 are needed")]
+    fn with_buffer_256_rejects_short_slice() {
+        let mut buffer = [0u8; 100];
+
+        Ssd1331::with_buffer_256(Spi, Pin, DisplayRotation::Rotate0, &mut buffer);
+    }
+
+    #[test]
+    fn with_buffer_256_sends_every_flush() {
+        let mut buffer = [0u8; BUF_SIZE];
+        let mut display = Ssd1331::with_buffer_256(
+            Spi,
+            Pin,
+            DisplayRotation::Rotate90,
+            &mut buffer[..BUF_SIZE_256],
+        );
+
+        assert_eq!(display.config.color_mode, ColorMode::CM256);
+        assert!(display
+            .init_with(ColorMode::CM65k, ByteOrder::BigEndian)
+            .is_err());
+
+        display.clear_color(0xffff);
+        #[cfg(feature = "graphics")]
+        display
+            .fill_solid(&display.bounding_box(), Rgb565::WHITE)
+            .unwrap();
+        display.set_rows(90, &[0xffff; 64 * 6]).unwrap();
+
+        display.flush().unwrap();
+        display.flush_full().unwrap();
+        display.flush_dirty().unwrap();
+        display.flush_yielding(|| ()).unwrap();
+        display.flush_rows(0, 96).unwrap();
+        display.flush_region((0, 0), (63, 95)).unwrap();
+
+        let mut ops = [Op::Command(&[]); 2];
+        assert_eq!(display.record_flush(&mut ops), 2);
+        assert_eq!(ops[1].bytes().len(), BUF_SIZE_256);
+
+        assert!(buffer[..BUF_SIZE_256].iter().all(|&b| b == 0xff));
+        assert!(buffer[BUF_SIZE_256..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn reset_timing() {
+        use core::cell::RefCell;
+
+        // Pin levels as 0 and 1, and delays as their length in milliseconds plus 100
+        struct Events(RefCell<([u8; 5], usize)>);
+
+        impl Events {
+            fn push(&self, event: u8) {
+                let (events, len) = &mut *self.0.borrow_mut();
+
+                events[*len] = event;
+                *len += 1;
+            }
+        }
+
+        impl OutputPin for &Events {
+            type Error = ();
+
+            fn set_high(&mut self) -> Result<(), ()> {
+                self.push(1);
+
+                Ok(())
+            }
+
+            fn set_low(&mut self) -> Result<(), ()> {
+                self.push(0);
+
+                Ok(())
+            }
+        }
+
+        impl DelayMs<u8> for &Events {
+            fn delay_ms(&mut self, ms: u8) {
+                self.push(100 + ms);
+            }
+        }
+
+        for (low, settle) in [(1, 1), (10, 2)].iter() {
+            let events = Events(RefCell::new(([0; 5], 0)));
+            let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+
+            if *low == 1 {
+                display.reset(&mut &events, &mut &events).unwrap();
+            } else {
+                display
+                    .reset_with_timing(&mut &events, &mut &events, *low, *settle)
+                    .unwrap();
+            }
+
+            assert_eq!(
+                events.0.into_inner(),
+                ([1, 100 + settle, 0, 100 + low, 1], 5)
+            );
+        }
+    }
+
+    #[test]
+    fn max_transfer_size_splits_frame() {
+        // Byte order, limit, then the number of pixel data writes
+        let cases = [
+            (ByteOrder::BigEndian, usize::MAX, 1),
+            (ByteOrder::BigEndian, 4000, 4),
+            (ByteOrder::LittleEndian, usize::MAX, BUF_SIZE / 64),
+            (ByteOrder::LittleEndian, 25, BUF_SIZE / 24),
+        ];
+
+        for (byte_order, size, writes) in cases.iter() {
+            let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+            display.byte_order = *byte_order;
+            display.set_max_transfer_size(*size);
+            display.flush().unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.writes, 1 + writes, "{:?} {}", byte_order, size);
+            assert_eq!(spi.bytes, FLUSH_HEADER_LEN + BUF_SIZE);
+        }
+
+        // Limit, then the number of pixel data writes for `flush_solid` and `draw_image_raw`
+        let cases = [
+            (usize::MAX, BUF_SIZE / 64, 1),
+            (4000, BUF_SIZE / 64, 4),
+            (25, BUF_SIZE / 64 * 3, BUF_SIZE.div_ceil(25)),
+        ];
+
+        for (size, solid_writes, image_writes) in cases.iter() {
+            let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+            display.set_max_transfer_size(*size);
+            display.flush_solid(0xf800).unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.writes, 1 + solid_writes, "solid {}", size);
+            assert_eq!(spi.bytes, FLUSH_HEADER_LEN + BUF_SIZE);
+
+            let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+            display.set_max_transfer_size(*size);
+            display
+                .draw_image_raw(&[0; BUF_SIZE], 96, ByteOrder::BigEndian, (0, 0))
+                .unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.writes, 1 + image_writes, "image {}", size);
+            assert_eq!(spi.bytes, FLUSH_HEADER_LEN + BUF_SIZE);
+        }
+    }
+
+    #[test]
+    fn fast_flush_skips_draw_area() {
+        let mut out = [0u8; FLUSH_HEADER_LEN * 3 + BUF_SIZE * 4];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_fast_flush(true);
+
+        // Draw area is unknown, so it's set
+        display.flush().unwrap();
+        // Skipped
+        display.flush().unwrap();
+        // Always set
+        display.flush_full().unwrap();
+        // Partial writes move the address pointer, so the next flush sets the draw area again
+        display.write_data(&[]).unwrap();
+        display.flush().unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+        let header = [0x15, 0, 95, 0x75, 0, 63];
+
+        assert_eq!(written.len(), FLUSH_HEADER_LEN * 3 + BUF_SIZE * 4);
+
+        let frame = FLUSH_HEADER_LEN + BUF_SIZE;
+
+        assert_eq!(written[0..FLUSH_HEADER_LEN], header);
+        assert_eq!(written[frame..frame + FLUSH_HEADER_LEN], [0; 6]);
+        assert_eq!(written[frame + BUF_SIZE..][0..FLUSH_HEADER_LEN], header);
+        assert_eq!(written[frame * 2 + BUF_SIZE..][0..FLUSH_HEADER_LEN], header);
+    }
+
+    #[test]
+    fn draw_area_is_one_write() {
+        let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+        display.set_draw_area((10, 20), (30, 40)).unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!((spi.writes, spi.bytes), (1, 6));
+
+        // Draw area commands, then the frame
+        let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+        display.byte_order = ByteOrder::BigEndian;
+        display.flush().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!((spi.writes, spi.bytes), (2, FLUSH_HEADER_LEN + BUF_SIZE));
+    }
+
+    #[test]
+    fn new_256_uses_half_buffer() {
+        let mut display = Ssd1331::new_256(CountingSpi::default(), Pin, DisplayRotation::Rotate90);
+
+        display.set_pixel(63, 95, 0xffff);
+        display.flush().unwrap();
+
+        assert_eq!(display.buffer_mut()[BUF_SIZE_256 - 1], 0xff);
+        assert!(matches!(
+            display.init_with(ColorMode::CM65k, ByteOrder::BigEndian),
+            Err(Error::InvalidArgument)
+        ));
+        assert_eq!(display.config.color_mode, ColorMode::CM256);
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(
+            (spi.writes, spi.bytes),
+            (2, FLUSH_HEADER_LEN + BUF_SIZE_256)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn draw_and_flush_sends_bounding_box() {
+        let mut out = [0u8; 64];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display
+            .draw_and_flush(
+                [(10, 5), (12, 4), (-1, 4), (11, 200)]
+                    .iter()
+                    .map(|(x, y)| Pixel(Point::new(*x, *y), Rgb565::new(0x1F, 0, 0))),
+            )
+            .unwrap();
+
+        display.draw_and_flush(core::iter::empty()).unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        // Window is columns 10-12, rows 4-5 and nothing is sent for the empty iterator
+        assert_eq!(written[0..6], [0x15, 10, 12, 0x75, 4, 5]);
+        assert_eq!(
+            written[6..],
+            [
+                0, 0, 0, 0, 0xf8, 0x00, // Row 4
+                0xf8, 0x00, 0, 0, 0, 0, // Row 5
+            ]
+        );
+    }
+
+    #[test]
+    fn spinner_end_points() {
+        let cases = [
+            (0, (58, 32)),
+            (90, (48, 42)),
+            (180, (38, 32)),
+            (270, (48, 22)),
+            (405, (55, 39)),
+        ];
+
+        for (angle, (x, y)) in cases.iter() {
+            let mut out = [0u8; 8];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display
+                .draw_spinner_accel((48, 32), 10, *angle, 0xffff)
+                .unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.written()[0..5], [0x21, 48, 32, *x, *y], "{}", angle);
+        }
+    }
+
+    #[test]
+    fn draw_line_accel_bounds() {
+        let mut out = [0u8; 16];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        display.draw_line_accel((1, 2), (63, 95), 0xf800).unwrap();
+
+        assert!(matches!(
+            display.draw_line_accel((0, 0), (64, 95), 0xf800),
+            Err(Error::InvalidArgument)
+        ));
+
+        let (spi, _dc) = display.release();
+
+        // Logical rows and columns are swapped in the controller's address space
+        assert_eq!(*spi.written(), [0x21, 2, 1, 95, 63, 0x3e, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn draw_rect_accel_fill() {
+        let mut out = [0u8; 32];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_software_rotation(DisplayRotation::Rotate180);
+        display
+            .draw_rect_accel((0, 0), (40, 11), 0xffff, Some(0x001f))
+            .unwrap();
+        display
+            .draw_rect_accel((0, 0), (40, 11), 0xffff, None)
+            .unwrap();
+
+        assert!(matches!(
+            display.draw_rect_accel((1, 0), (0, 11), 0xffff, None),
+            Err(Error::InvalidArgument)
+        ));
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        // Corners are flipped by the rotation and sent in order
+        assert_eq!(
+            written[0..13],
+            [0x26, 0x01, 0x22, 55, 52, 95, 63, 0x3e, 0x3f, 0x3e, 0x00, 0x00, 0x3e]
+        );
+        assert_eq!(written[13..15], [0x26, 0x00]);
+        assert_eq!(
+            written[15..26],
+            [0x22, 55, 52, 95, 63, 0x3e, 0x3f, 0x3e, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn with_draw_area_matches_with_window() {
+        let mut tuples = [0u8; 32];
+        let mut typed = [0u8; 32];
+
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut tuples), Pin, DisplayRotation::Rotate0);
+        display
+            .with_draw_area((10, 10), (11, 11), |d| d.write_data(&[0xff; 8]))
+            .unwrap()
+            .unwrap();
+        let (tuples, _dc) = display.release();
+
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut typed), Pin, DisplayRotation::Rotate0);
+        let area = DrawArea::new((10, 10), (11, 11)).unwrap();
+        display
+            .with_window(area, |d| d.write_data(&[0xff; 8]))
+            .unwrap()
+            .unwrap();
+        let (typed, _dc) = display.release();
+
+        assert_eq!(tuples.written(), typed.written());
+        assert_eq!(tuples.written()[14..], FULL_DRAW_AREA);
+    }
+
+    #[test]
+    fn clear_window_leaves_framebuffer() {
+        let mut out = [0u8; 8];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        display.set_pixel(1, 2, 0xffff);
+        display.clear_window((0, 0), (10, 20)).unwrap();
+
+        assert_eq!(pixel(&display, 1, 2), 0xffff);
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(*spi.written(), [0x25, 0, 0, 20, 10]);
+    }
+
+    #[test]
+    fn set_multiplex_validates_ratio() {
+        let mut out = [0u8; 4];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_multiplex(35).unwrap();
+        display.set_multiplex(63).unwrap();
+
+        for ratio in [0, 14, 64, 0xff].iter() {
+            assert!(matches!(
+                display.set_multiplex(*ratio),
+                Err(Error::InvalidArgument)
+            ));
+        }
+
+        assert_eq!(display.config.multiplex, 63);
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0xA8, 35, 0xA8, 63]);
+    }
+
+    #[test]
+    fn set_start_line_masks_line() {
+        for line in [0, 5, 63, 64, 0x45, 0xff].iter() {
+            let mut out = [0u8; 2];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display.set_start_line(*line).unwrap();
+
+            assert_eq!(display.config.start_line, line & 0x3F);
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.written(), [0xA1, line & 0x3F]);
+        }
+    }
+
+    #[test]
+    fn set_display_offset_validates_offset() {
+        let mut out = [0u8; 2];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_display_offset(16).unwrap();
+
+        assert!(matches!(
+            display.set_display_offset(64),
+            Err(Error::InvalidArgument)
+        ));
+        assert_eq!(display.config.display_offset, 16);
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0xA2, 16]);
+    }
+
+    #[test]
+    fn scroll_commands() {
+        let mut out = [0u8; 16];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.start_scroll(1, 8, 16, 0, NFrames::F10).unwrap();
+        display.stop_scroll().unwrap();
+
+        assert!(matches!(
+            display.start_scroll(1, 56, 9, 0, NFrames::F10),
+            Err(Error::InvalidArgument)
+        ));
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(*spi.written(), [0x2E, 0x27, 1, 8, 16, 0, 0b01, 0x2F, 0x2E]);
+    }
+
+    #[test]
+    fn copy_window_regions() {
+        let cases = [
+            (DisplayRotation::Rotate0, false, [2, 0, 95, 7, 0, 0]),
+            (DisplayRotation::Rotate90, false, [0, 2, 7, 63, 0, 0]),
+            // The unrotated copy runs the other way
+            (DisplayRotation::Rotate180, true, [0, 56, 93, 63, 2, 56]),
+        ];
+
+        for (rotation, software, expected) in cases.iter() {
+            let mut out = [0u8; 8];
+            let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, *rotation);
+
+            if *software {
+                display.set_software_rotation(*rotation);
+            }
+
+            let (w, _) = display.dimensions();
+
+            display.copy_window(((2, 0), (w - 1, 7)), (0, 0)).unwrap();
+
+            assert!(matches!(
+                display.copy_window(((0, 0), (w - 1, 7)), (1, 0)),
+                Err(Error::InvalidArgument)
+            ));
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(spi.written()[0], 0x23);
+            assert_eq!(spi.written()[1..], *expected, "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn crosshair_lines() {
+        let cases = [
+            (
+                DisplayRotation::Rotate0,
+                (40, 25),
+                [0, 25, 95, 25],
+                [40, 0, 40, 63],
+            ),
+            (
+                DisplayRotation::Rotate0,
+                (200, 200),
+                [0, 63, 95, 63],
+                [95, 0, 95, 63],
+            ),
+            // Logical rows and columns are swapped in the controller's address space
+            (
+                DisplayRotation::Rotate90,
+                (40, 25),
+                [25, 0, 25, 63],
+                [0, 40, 95, 40],
+            ),
+        ];
+
+        for (rotation, at, horizontal, vertical) in cases.iter() {
+            let mut out = [0u8; 16];
+            let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, *rotation);
+
+            display.draw_crosshair_accel(*at, 0xffff).unwrap();
+
+            let (spi, _dc) = display.release();
+            let written = spi.written();
+
+            assert_eq!(written[0], 0x21);
+            assert_eq!(written[1..5], *horizontal, "{:?} {:?}", rotation, at);
+            assert_eq!(written[8], 0x21);
+            assert_eq!(written[9..13], *vertical, "{:?} {:?}", rotation, at);
+        }
+    }
+
+    #[test]
+    fn fill_circle_spans() {
+        // Center, radius, then the start and end column and row of each rectangle
+        type Case = ((u8, u8), u8, &'static [[u8; 4]]);
+
+        let cases: [Case; 3] = [
+            ((10, 10), 0, &[[10, 10, 10, 10]]),
+            (
+                (10, 10),
+                2,
+                &[
+                    [10, 8, 10, 8],
+                    [9, 9, 11, 9],
+                    [8, 10, 12, 10],
+                    [9, 11, 11, 11],
+                    [10, 12, 10, 12],
+                ],
+            ),
+            // Clipped to the top left corner
+            ((0, 0), 2, &[[0, 0, 2, 0], [0, 1, 1, 1], [0, 2, 0, 2]]),
+        ];
+
+        for (center, radius, spans) in cases.iter() {
+            let mut out = [0u8; 64];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display.fill_circle_accel(*center, *radius, 0xffff).unwrap();
+
+            let (spi, _dc) = display.release();
+            let written = spi.written();
+            let (fill_on, rest) = written.split_at(2);
+            let (rects, fill_off) = rest.split_at(rest.len() - 2);
+
+            assert_eq!(*fill_on, [0x26, 0x01]);
+            assert_eq!(*fill_off, [0x26, 0x00]);
+            assert_eq!(rects.len(), spans.len() * 11, "{:?} {}", center, radius);
+
+            for (rect, span) in rects.chunks(11).zip(spans.iter()) {
+                assert_eq!(rect[0], 0x22);
+                assert_eq!(rect[1..5], *span, "{:?} {}", center, radius);
+            }
+        }
+    }
+
+    #[test]
+    fn draw_area_bounds_policy() {
+        let mut out = [0u8; 16];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        // Strict by default, sending nothing
+        assert!(matches!(
+            display.set_draw_area((90, 60), (96, 63)),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            display.set_draw_area((90, 60), (95, 64)),
+            Err(Error::InvalidArgument)
+        ));
+
+        display.set_bounds_policy(BoundsPolicy::Clamp);
+
+        assert!(display.set_draw_area((90, 60), (200, 200)).is_ok());
+        // Reversed corners can't be clamped
+        assert!(matches!(
+            display.set_draw_area((200, 60), (90, 63)),
+            Err(Error::InvalidArgument)
+        ));
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0x15, 90, 95, 0x75, 60, 63]);
+    }
+
+    #[test]
+    fn invert_region_rotated() {
+        let mut out = [0u8; 64];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        display.set_pixel(63, 95, 0x1234);
+        display.invert_region((62, 94), (200, 200)).unwrap();
+
+        assert_eq!(pixel(&display, 63, 95), 0xedcb);
+        assert_eq!(pixel(&display, 62, 94), 0xffff);
+        assert_eq!(pixel(&display, 61, 94), 0x0000);
+
+        let (spi, _dc) = display.release();
+
+        // Logical columns 62-63 and rows 94-95 are controller rows 62-63 and columns 94-95
+        assert_eq!(spi.written()[0..6], [0x15, 94, 95, 0x75, 62, 63]);
+        assert_eq!(spi.written()[6..].len(), 2 * 2 * 2);
+    }
+
+    #[test]
+    fn highlight_region_scales_channels() {
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90].iter() {
+            let mut display = Ssd1331::new(Spi, Pin, *rotation);
+
+            display.dirty = None;
+            display.set_pixel(5, 6, 0x1234);
+            display.set_pixel(7, 6, 0x1234);
+            display.highlight_region((4, 5), (6, 200), 32).unwrap();
+
+            // Green is doubled, blue is clamped to its maximum
+            assert_eq!(pixel(&display, 5, 6), 0x245f, "{:?}", rotation);
+            assert_eq!(pixel(&display, 7, 6), 0x1234, "{:?}", rotation);
+
+            display.highlight_region((5, 6), (5, 6), 8).unwrap();
+
+            assert_eq!(pixel(&display, 5, 6), 0x122f, "{:?}", rotation);
+
+            let (_, h) = display.dimensions();
+
+            assert_eq!(display.dirty, Some(((4, 5), (7, h - 1))), "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn clear_active_skips_inactive_rows() {
+        let cases = [
+            (DisplayRotation::Rotate0, (0, 35), (0, 36)),
+            (DisplayRotation::Rotate90, (35, 0), (36, 0)),
+        ];
+
+        for (rotation, active, inactive) in cases.iter() {
+            let mut display = Ssd1331::new(Spi, Pin, *rotation);
+
+            display.config.multiplex = 35;
+            display.clear_active(0xffff);
+
+            assert_eq!(
+                pixel(&display, active.0, active.1),
+                0xffff,
+                "{:?}",
+                rotation
+            );
+            assert_eq!(pixel(&display, inactive.0, inactive.1), 0, "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn frame_count_wraps() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+
+        display.frame_count = u32::MAX;
+        display.flush().unwrap();
+
+        assert_eq!(display.frame_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn draw_iter_spans_match_pixels() {
+        let red = Rgb565::new(0x1F, 0, 0);
+        let blue = Rgb565::new(0, 0, 0x1F);
+
+        // Runs broken by colour, row and gaps, plus off screen pixels
+        let pixels = [
+            (-2, 0, red),
+            (-1, 0, red),
+            (0, 0, red),
+            (1, 0, red),
+            (2, 0, blue),
+            (3, 0, blue),
+            (5, 0, blue),
+            (62, 1, red),
+            (63, 1, red),
+            (64, 1, red),
+            (0, 2, red),
+            (1, 2, red),
+            (0, 2, blue),
+        ];
+
+        for software in [false, true].iter() {
+            for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90].iter() {
+                let mut display = Ssd1331::new(Spi, Pin, *rotation);
+                let mut expected = Ssd1331::new(Spi, Pin, *rotation);
+
+                if *software {
+                    display.set_software_rotation(*rotation);
+                    expected.set_software_rotation(*rotation);
+                }
+
+                display
+                    .draw_iter(
+                        pixels
+                            .iter()
+                            .map(|(x, y, color)| Pixel(Point::new(*x, *y), *color)),
+                    )
+                    .unwrap();
+
+                for (x, y, color) in pixels.iter() {
+                    if *x >= 0 {
+                        expected.set_pixel(*x as u32, *y as u32, RawU16::from(*color).into_inner());
+                    }
+                }
+
+                assert!(
+                    display.buffer[..] == expected.buffer[..],
+                    "{:?}, software rotation {}",
+                    rotation,
+                    software
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn flush_dirty_sends_changed_region() {
+        // Two full frames and one 4x3 pixel region
+        const LEN: usize = FLUSH_HEADER_LEN * 3 + BUF_SIZE * 2 + 4 * 3 * 2;
+
+        let mut out = [0u8; LEN];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        // Everything is dirty to start with
+        display.flush_dirty().unwrap();
+        // Nothing changed
+        display.flush_dirty().unwrap();
+
+        display.set_pixel(10, 20, 0xffff);
+        display.set_pixel(13, 22, 0xffff);
+        display.flush_dirty().unwrap();
+
+        display.mark_all_dirty();
+        display.flush().unwrap();
+        // Cleared by the full flush
+        display.flush_dirty().unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        assert_eq!(written.len(), LEN);
+        assert_eq!(written[0..FLUSH_HEADER_LEN], [0x15, 0, 95, 0x75, 0, 63]);
+
+        // Logical columns 10-13 and rows 20-22 are controller rows and columns
+        let region = &written[FLUSH_HEADER_LEN + BUF_SIZE..];
+        assert_eq!(region[0..FLUSH_HEADER_LEN], [0x15, 20, 22, 0x75, 10, 13]);
+    }
+
+    #[test]
+    fn pixel_index_corners() {
+        // Rotation, software rotation, then the offsets of the top left, top right, bottom left
+        // and bottom right pixels
+        type Case = (DisplayRotation, bool, [usize; 4]);
+
+        let wide = [0, 95 * 2, 63 * 96 * 2, (63 * 96 + 95) * 2];
+        let tall = [0, 63 * 2, 95 * 64 * 2, (95 * 64 + 63) * 2];
+
+        let cases: [Case; 6] = [
+            (DisplayRotation::Rotate0, false, wide),
+            (DisplayRotation::Rotate90, false, tall),
+            (DisplayRotation::Rotate180, false, wide),
+            (DisplayRotation::Rotate270, false, tall),
+            // Software rotation keeps the unrotated layout
+            (
+                DisplayRotation::Rotate90,
+                true,
+                [wide[1], wide[3], wide[0], wide[2]],
+            ),
+            (
+                DisplayRotation::Rotate270,
+                true,
+                [wide[2], wide[0], wide[3], wide[1]],
+            ),
+        ];
+
+        for (rotation, software, expected) in cases.iter() {
+            let mut display = Ssd1331::new(Spi, Pin, *rotation);
+
+            if *software {
+                display.set_software_rotation(*rotation);
+            }
+
+            let (w, h) = display.dimensions();
+            let (w, h) = (u32::from(w), u32::from(h));
+
+            let corners = [(0, 0), (w - 1, 0), (0, h - 1), (w - 1, h - 1)];
+
+            for ((x, y), offset) in corners.iter().zip(expected.iter()) {
+                assert_eq!(
+                    display.pixel_index(*x, *y),
+                    Some(*offset),
+                    "{:?} ({}, {})",
+                    rotation,
+                    x,
+                    y
+                );
+            }
+
+            assert_eq!(display.pixel_index(w, 0), None, "{:?}", rotation);
+            assert_eq!(display.pixel_index(0, h), None, "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn get_pixel_reads_back_set_pixel() {
+        let rotations = [
+            DisplayRotation::Rotate0,
+            DisplayRotation::Rotate90,
+            DisplayRotation::Rotate180,
+            DisplayRotation::Rotate270,
+        ];
+
+        for rotation in rotations.iter() {
+            for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian].iter() {
+                let mut display = Ssd1331::new(Spi, Pin, *rotation);
+                display.byte_order = *byte_order;
+
+                let (w, h) = display.dimensions();
+                let (w, h) = (u32::from(w), u32::from(h));
+
+                display.set_pixel(w - 1, h - 1, 0x1234);
+
+                assert_eq!(display.get_pixel(w - 1, h - 1), Some(0x1234));
+                assert_eq!(display.get_pixel(0, 0), Some(0));
+                assert_eq!(display.get_pixel(0, h), None);
+            }
+        }
+    }
+
+    #[test]
+    fn flush_region_sends_rotated_column() {
+        let mut out = [0u8; FLUSH_HEADER_LEN + 96 * 2];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        display.byte_order = ByteOrder::BigEndian;
+        display.set_pixel(5, 95, 0xf800);
+
+        display.flush_region((5, 0), (5, 95)).unwrap();
+
+        assert!(matches!(
+            display.flush_region((5, 0), (64, 95)),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            display.flush_region((5, 1), (5, 0)),
+            Err(Error::InvalidArgument)
+        ));
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        // Logical column 5 is controller row 5
+        assert_eq!(written[0..FLUSH_HEADER_LEN], [0x15, 0, 95, 0x75, 5, 5]);
+        assert_eq!(written.len(), FLUSH_HEADER_LEN + 96 * 2);
+        assert_eq!(written[written.len() - 2..], [0xf8, 0x00]);
+    }
+
+    #[test]
+    fn flush_dirty_keeps_region_on_error() {
+        // Room for the draw area commands but not the pixel data
+        let mut out = [0u8; FLUSH_HEADER_LEN];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.dirty = None;
+        display.set_pixel(10, 20, 0xffff);
+
+        assert!(display.flush_dirty().is_err());
+        assert_eq!(display.dirty, Some(((10, 20), (10, 20))));
+
+        assert!(display.flush().is_err());
+        assert_eq!(display.dirty, Some(((10, 20), (10, 20))));
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn draw_line_aa_blends_both_sides() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+        let white = RawU16::from(Rgb565::WHITE).into_inner();
+
+        // Shallow line with a slope of 1/4
+        display.draw_line_aa(Point::new(0, 0), Point::new(8, 2), Rgb565::WHITE);
+
+        // End points are solid
+        assert_eq!(pixel(&display, 0, 0), white);
+        assert_eq!(pixel(&display, 8, 2), white);
+
+        // Half way between two rows, both get about half coverage
+        let above = Rgb565::from(RawU16::new(pixel(&display, 2, 0)));
+        let below = Rgb565::from(RawU16::new(pixel(&display, 2, 1)));
+
+        assert!((15..=16).contains(&above.r()), "{:?}", above);
+        assert!((15..=16).contains(&below.r()), "{:?}", below);
+
+        // Mostly on row 1 at x = 4
+        let on = Rgb565::from(RawU16::new(pixel(&display, 4, 1)));
+        assert_eq!(on, Rgb565::WHITE);
+        assert_eq!(pixel(&display, 4, 2), 0);
+
+        // Off screen parts are skipped
+        display.draw_line_aa(Point::new(-10, -3), Point::new(100, 70), Rgb565::WHITE);
+    }
+
+    #[test]
+    fn set_pixel_formats() {
+        let cases = [
+            (ColorMode::CM65k, ByteOrder::BigEndian, [0xf8, 0x1f]),
+            (ColorMode::CM65k, ByteOrder::LittleEndian, [0x1f, 0xf8]),
+            (ColorMode::CM256, ByteOrder::BigEndian, [0xe3, 0x00]),
+            (ColorMode::CM256, ByteOrder::LittleEndian, [0xe3, 0x00]),
+        ];
+
+        for (color_mode, byte_order, bytes) in cases.iter() {
+            let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+            display.init_with(*color_mode, *byte_order).unwrap();
+
+            let bpp = display.bytes_per_pixel();
+
+            // Magenta at the second pixel of the second row
+            display.set_pixel(1, 1, 0xf81f);
+
+            let idx = (96 + 1) * bpp;
+            let buffer = display.buffer_mut();
+
+            assert_eq!(buffer.len(), 96 * 64 * bpp);
+            assert_eq!(
+                buffer[idx..idx + bpp],
+                bytes[0..bpp],
+                "{:?} {:?}",
+                color_mode,
+                byte_order
+            );
+            assert!(buffer[0..idx].iter().all(|b| *b == 0));
+            assert!(buffer[idx + bpp..].iter().all(|b| *b == 0));
+        }
+    }
+
+    #[test]
+    fn cm256_flush_sends_half_frame() {
+        let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+        display
+            .init_with(ColorMode::CM256, ByteOrder::BigEndian)
+            .unwrap();
+
+        let (spi, _dc) = display.release();
+        let init_bytes = spi.bytes;
+
+        let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+        display
+            .init_with(ColorMode::CM256, ByteOrder::BigEndian)
+            .unwrap();
+        display.flush().unwrap();
+        display.flush_solid(0xffff).unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.bytes - init_bytes, (FLUSH_HEADER_LEN + 96 * 64) * 2);
+    }
+
+    #[test]
+    fn rgb332_round_trip() {
+        for value in 0..=255u8 {
+            assert_eq!(rgb565_to_rgb332(rgb332_to_rgb565(value)), value);
+        }
+
+        assert_eq!(rgb332_to_rgb565(0xff), 0xffff);
+        assert_eq!(rgb332_to_rgb565(0x00), 0x0000);
+    }
+
+    #[test]
+    fn dithering_mixes_nearest_levels() {
+        // Red halfway between the two lowest RGB332 levels
+        let value = 2 << 11;
+
+        let mut display = Ssd1331::new_256(Spi, Pin, DisplayRotation::Rotate0);
+
+        for (x, y) in (0..4).flat_map(|y| (0..4).map(move |x| (x, y))) {
+            display.set_pixel(x, y, value);
+        }
+
+        assert!((0..4).all(|y| display.buffer_mut()[y * 96..y * 96 + 4] == [0; 4]));
+
+        display.set_dithering(true);
+
+        for (x, y) in (0..4).flat_map(|y| (0..4).map(move |x| (x, y))) {
+            display.set_pixel(x, y, value);
+            display.set_pixel(x + 4, y, 0xffff);
+        }
+
+        let block = display
+            .buffer_mut()
+            .chunks(96)
+            .take(4)
+            .flat_map(|row| &row[0..4]);
+
+        assert!(block.clone().all(|p| *p == 0x00 || *p == 0x20));
+        assert_eq!(block.filter(|p| **p == 0x20).count(), 8);
+        // Full intensity saturates instead of wrapping
+        assert!((0..4).all(|y| display.buffer_mut()[y * 96 + 4..y * 96 + 8] == [0xff; 4]));
+    }
+
+    #[test]
+    fn dithering_clear_matches_set_pixel() {
+        let mut cleared = Ssd1331::new_256(Spi, Pin, DisplayRotation::Rotate0);
+        let mut expected = Ssd1331::new_256(Spi, Pin, DisplayRotation::Rotate0);
+
+        cleared.set_dithering(true);
+        expected.set_dithering(true);
+
+        cleared.clear_color(0x7bef);
+
+        for (x, y) in (0..96).flat_map(|x| (0..64).map(move |y| (x, y))) {
+            expected.set_pixel(x, y, 0x7bef);
+        }
+
+        assert!(cleared.buffer_mut() == expected.buffer_mut());
+        // Mid grey falls between levels, so a pattern is drawn rather than a flat colour
+        let first = cleared.buffer_mut()[0];
+        assert!(cleared.buffer_mut().iter().any(|p| *p != first));
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn dithering_fill_matches_set_pixel() {
+        let area = Rectangle::new(Point::new(3, 5), Size::new(40, 20));
+
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90].iter() {
+            let mut filled = Ssd1331::new_256(Spi, Pin, *rotation);
+            let mut expected = Ssd1331::new_256(Spi, Pin, *rotation);
+
+            filled.set_dithering(true);
+            expected.set_dithering(true);
+
+            filled.fill_solid(&area, Rgb565::new(10, 20, 10)).unwrap();
+
+            for p in area.points() {
+                expected.set_pixel_at(p, Rgb565::new(10, 20, 10));
+            }
+
+            assert!(filled.buffer_mut() == expected.buffer_mut());
+        }
+    }
+
+    #[test]
+    fn set_contrast_keeps_brightness() {
+        let mut out = [0u8; 16];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_contrast(0xff, 0x80, 0x40).unwrap();
+        display.config.brightness = 63;
+        display.set_contrast(0xff, 0x80, 0x40).unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        assert_eq!(
+            written[0..8],
+            [0x81, 0xff, 0x82, 0x80, 0x83, 0x40, 0x87, 0x0F]
+        );
+        // A quarter of the master current, with the contrast unscaled
+        assert_eq!(
+            written[8..],
+            [0x81, 0xff, 0x82, 0x80, 0x83, 0x40, 0x87, 0x03]
+        );
+    }
+
+    #[test]
+    fn brightness_split() {
+        // (level, contrast, master current)
+        let cases = [
+            (255, [0x91, 0x50, 0x7D], 15),
+            (127, [0x91, 0x50, 0x7D], 7),
+            (100, [0x82, 0x48, 0x70], 6),
+            (0, [0x09, 0x05, 0x07], 0),
+        ];
+
+        for (level, contrast, master) in cases.iter() {
+            let mut out = [0u8; 8];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display.set_brightness(*level).unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(
+                spi.written(),
+                [
+                    0x81,
+                    contrast[0],
+                    0x82,
+                    contrast[1],
+                    0x83,
+                    contrast[2],
+                    0x87,
+                    *master
+                ],
+                "level {}",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn set_vcomh_sends_level() {
+        let mut out = [0u8; 2];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_vcomh(VcomhLevel::V083).unwrap();
+
+        assert_eq!(display.config.vcomh, VcomhLevel::V083);
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0xBE, 0x3E]);
+    }
+
+    #[test]
+    fn set_precharge_voltage_clamps_level() {
+        let mut out = [0u8; 4];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_precharge_voltage(0x1D).unwrap();
+        display.set_precharge_voltage(0xFF).unwrap();
+
+        assert_eq!(display.config.precharge_voltage, Some(31));
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0xBB, 0x3A, 0xBB, 0x3E]);
+    }
+
+    #[test]
+    fn set_display_clock_masks_values() {
+        let mut out = [0u8; 2];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_display_clock(0x18, 0x23).unwrap();
+
+        assert_eq!(display.config.display_clock, (0x8, 0x3));
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(spi.written(), [0xB3, 0x83]);
+    }
+
+    #[test]
+    fn gamma_table() {
+        let mut out = [0u8; 34];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        let mut table = [0u8; 32];
+
+        for (n, width) in table.iter_mut().enumerate() {
+            *width = n as u8 * 4 + 1;
+        }
+
+        display.set_gamma(&table).unwrap();
+
+        assert_eq!(display.config.gamma, Some(table));
+
+        display.reset_gamma().unwrap();
+
+        assert_eq!(display.config.gamma, None);
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        assert_eq!(written[0], 0xB8);
+        assert_eq!(written[1..33], table);
+        assert_eq!(written[33], 0xB9);
+    }
+
+    #[test]
+    fn master_current_keeps_contrast() {
+        for (current, master) in [(6, 6), (15, 15), (200, 15)].iter() {
+            let mut out = [0u8; 8];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display.set_master_current(*current).unwrap();
+
+            let (spi, _dc) = display.release();
+
+            assert_eq!(
+                spi.written(),
+                [0x81, 0x91, 0x82, 0x50, 0x83, 0x7D, 0x87, *master]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphics")]
+    fn init_clearing_fills_display_and_buffer() {
+        let mut out = [0u8; 64];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate90);
+
+        display.init_clearing(Rgb565::RED, &mut Delay).unwrap();
+
+        assert!((0..64).all(|x| (0..96).all(|y| pixel(&display, x, y) == 0xf800)));
+        assert_eq!(display.dirty, None);
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+        let (fill, display_on) = written[written.len() - 16..].split_at(15);
+
+        assert_eq!(
+            fill,
+            [0x26, 0x01, 0x22, 0, 0, 95, 63, 0x3e, 0, 0, 0x3e, 0, 0, 0x26, 0x00]
+        );
+        assert_eq!(display_on, [0xAF]);
+    }
+
+    #[test]
+    fn downscale_averages_blocks() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate90);
+
+        // Top left 2x2 block: two white and two black pixels
+        display.set_pixel(0, 0, 0xffff);
+        display.set_pixel(1, 1, 0xffff);
+        // Next block: one pixel of each channel at full intensity, and one black
+        display.set_pixel(2, 0, 0xf800);
+        display.set_pixel(3, 0, 0x07e0);
+        display.set_pixel(2, 1, 0x001f);
+
+        let mut out = [0u16; 32 * 48];
+
+        assert_eq!(display.downscale(2, &mut out).unwrap(), (32, 48));
+        // 31 * 2 / 4 and 63 * 2 / 4 round up
+        assert_eq!(out[0], (16 << 11) | (32 << 5) | 16);
+        // 31 / 4 rounds down, 63 / 4 rounds up
+        assert_eq!(out[1], (8 << 11) | (16 << 5) | 8);
+        assert!(out[2..].iter().all(|p| *p == 0));
+
+        // Partial blocks along the edges only average the pixels they contain
+        for y in 90..96 {
+            for x in 60..64 {
+                display.set_pixel(x, y, 0xffff);
+            }
+        }
+
+        let mut out = [0u16; 7 * 10];
+
+        assert_eq!(display.downscale(10, &mut out).unwrap(), (7, 10));
+        assert_eq!(out[7 * 10 - 1], 0xffff);
+
+        assert!(display.downscale(0, &mut out).is_err());
+        assert!(display.downscale(9, &mut out).is_err());
+    }
+
+    #[test]
+    fn fade_ramps_brightness() {
+        let full = [0x81, 0x91, 0x82, 0x50, 0x83, 0x7D, 0x87, 0x0F];
+        let half = [0x81, 0x91, 0x82, 0x50, 0x83, 0x7D, 0x87, 0x07];
+        let black = [0x81, 0, 0x82, 0, 0x83, 0];
+
+        let mut out = [0u8; 64];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.turn_on_faded(&mut Delay, 2).unwrap();
+        display.turn_off_faded(&mut Delay, 2).unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        // Fade in: black, display on, then up to full brightness
+        assert_eq!(written[0..6], black);
+        assert_eq!(written[6], 0xAF);
+        assert_eq!(written[7..15], half);
+        assert_eq!(written[15..23], full);
+
+        // Fade out: down to black, display off, then full brightness restored
+        assert_eq!(written[23..31], half);
+        assert_eq!(written[31..37], black);
+        assert_eq!(written[37], 0xAE);
+        assert_eq!(written[38..], full);
+
+        for steps in [0, 1, 20].iter() {
+            let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+            display.turn_on_faded(&mut Delay, *steps).unwrap();
+
+            let (on, _dc) = display.release();
+            let mut display = Ssd1331::new(CountingSpi::default(), Pin, DisplayRotation::Rotate0);
+
+            display.turn_off_faded(&mut Delay, *steps).unwrap();
+
+            let (off, _dc) = display.release();
+            let writes = 2 + 2 * usize::from((*steps).max(1));
+
+            assert_eq!((on.writes, off.writes), (writes, writes), "{} steps", steps);
+        }
+    }
+
+    #[test]
+    fn analog_builders_applied_by_init() {
+        let mut out = [0u8; 32];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0)
+            .with_contrast(0x80, 0x48, 0x70)
+            .with_master_current(0x0C)
+            .with_precharge_period(0x2, 0xE)
+            .with_precharge_voltage(0x1D)
+            .with_vcomh(VcomhLevel::V083)
+            .with_clear_on_init(false);
+
+        display.init().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        // After the display off, clock, multiplex, start line, offset and remap commands
+        assert_eq!(
+            spi.written()[11..],
+            [
+                0x81, 0x80, 0x82, 0x48, 0x83, 0x70, // Contrast
+                0x87, 0x0C, // Master current
+                0xB1, 0xE2, // Precharge period
+                0xBB, 0x3A, // Precharge voltage
+                0xBE, 0x3E, // Vcomh deselect level
+                0xA4, // Normal (non-inverted) display
+                0xAF, // Display on
+            ]
+        );
+    }
+
+    #[test]
+    fn init_with_delay_matches_init() {
+        struct TotalDelay<'a>(&'a Cell<u32>);
+
+        impl DelayMs<u8> for TotalDelay<'_> {
+            fn delay_ms(&mut self, ms: u8) {
+                self.0.set(self.0.get() + u32::from(ms));
+            }
+        }
+
+        let mut plain = [0u8; 32];
+        let mut delayed = [0u8; 32];
+        let total = Cell::new(0);
+
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut plain), Pin, DisplayRotation::Rotate0);
+        display.set_clear_on_init(false);
+        display.init().unwrap();
+        let (plain_spi, _dc) = display.release();
+
+        let mut display =
+            Ssd1331::new(CaptureSpi::new(&mut delayed), Pin, DisplayRotation::Rotate0);
+        display.set_clear_on_init(false);
+        display.init_with_delay(&mut TotalDelay(&total)).unwrap();
+        let (delayed_spi, _dc) = display.release();
+
+        assert_eq!(plain_spi.written(), delayed_spi.written());
+        assert_eq!(total.get(), 110);
+    }
+
+    #[test]
+    fn turn_on_restoring_flushes_first() {
+        let mut out = [0u8; FLUSH_HEADER_LEN + BUF_SIZE + 1];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.byte_order = ByteOrder::BigEndian;
+        display.set_pixel(0, 0, 0xabcd);
+        display.turn_on_restoring().unwrap();
+
+        let (spi, _dc) = display.release();
+        let written = spi.written();
+
+        assert_eq!(written[0..FLUSH_HEADER_LEN], [0x15, 0, 95, 0x75, 0, 63]);
+        assert_eq!(written[FLUSH_HEADER_LEN..][0..2], [0xab, 0xcd]);
+        assert_eq!(written[written.len() - 1], 0xAF);
+    }
+
+    #[test]
+    fn set_invert_respects_blanking() {
+        let mut out = [0u8; 8];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_invert(true).unwrap();
+        display.set_invert(false).unwrap();
+        display.blank().unwrap();
+        display.set_invert(true).unwrap();
+        display.unblank().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(*spi.written(), [0xA7, 0xA4, 0xA6, 0xA6, 0xA7]);
+    }
+
+    #[test]
+    fn shutdown_fades_and_turns_off() {
+        let mut out = [0u8; 128];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.config.display_on = true;
+
+        let (spi, _dc) = display.shutdown(&mut Delay);
+        let written = spi.written();
+        let black = [0x81, 0, 0x82, 0, 0x83, 0];
+
+        assert_eq!(written[0], 0x2E);
+        // Fade ends with the contrast at zero, the display off, then brightness restored
+        assert_eq!(written[written.len() - 15..written.len() - 9], black);
+        assert_eq!(written[written.len() - 9], 0xAE);
+
+        // The fade fails as its first command doesn't fit, but the display is still turned off
+        let mut out = [0u8; 4];
+        let display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+        let (spi, _dc) = display.shutdown(&mut Delay);
+
+        assert_eq!(*spi.written(), [0x2E, 0xAE]);
+    }
+
+    #[test]
+    fn all_on_overrides_other_modes() {
+        let mut out = [0u8; 8];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        display.set_invert(true).unwrap();
+        display.set_all_on(true).unwrap();
+        display.blank().unwrap();
+        display.set_all_on(false).unwrap();
+        display.unblank().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        assert_eq!(*spi.written(), [0xA7, 0xA5, 0xA5, 0xA6, 0xA7]);
+    }
+
+    #[test]
+    fn self_test_restores_display_mode() {
+        for inverted in [false, true].iter() {
+            let mut out = [0u8; 32];
+            let mut display =
+                Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+            display.set_clear_on_init(false);
+            display.config.inverted = *inverted;
+            display.init_with_self_test(&mut Delay).unwrap();
+
+            let (spi, _dc) = display.release();
+            let written = spi.written();
+            let restored = if *inverted { 0xA7 } else { 0xA4 };
+
+            // Display on, two flashes, then back to the previous mode
+            assert_eq!(
+                written[written.len() - 6..],
+                [0xAF, 0xA5, 0xA6, 0xA5, 0xA6, restored]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "le-buffer")]
+    fn le_buffer_flushes_big_endian() {
+        let mut out = [0u8; FLUSH_HEADER_LEN + BUF_SIZE];
+        let mut display = Ssd1331::new(CaptureSpi::new(&mut out), Pin, DisplayRotation::Rotate0);
+
+        // Little endian RGB565 red, as stored in an `ImageRawLE` asset, copied straight in
+        display.buffer_mut()[0..2].copy_from_slice(&[0x00, 0xf8]);
+        // Green set through the driver is stored little endian too
+        display.set_pixel(1, 0, 0x07e0);
+        assert_eq!(display.buffer_mut()[2..4], [0xe0, 0x07]);
+
+        display.flush().unwrap();
+
+        let (spi, _dc) = display.release();
+        let data = &spi.written()[FLUSH_HEADER_LEN..];
+
+        assert_eq!(data[0..4], [0xf8, 0x00, 0x07, 0xe0]);
     }
 }
@@ -1,7 +1,9 @@
 /// Display rotation
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DisplayRotation {
     /// No rotation, normal display
+    #[default]
     Rotate0,
     /// Rotate by 90 degrees clockwise
     Rotate90,
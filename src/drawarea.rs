@@ -0,0 +1,77 @@
+use crate::{error::Error, DISPLAY_HEIGHT, DISPLAY_WIDTH};
+
+/// A rectangular window in the controller's (column, row) address space
+///
+/// Both corners are inclusive. A `DrawArea` can only be created through [`DrawArea::new`], which
+/// guarantees that `top_left` is above and to the left of (or equal to) `bottom_right`, and that
+/// both corners are inside the 96 column by 64 row address space. Methods that take a `DrawArea`
+/// therefore don't need to check it again.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssd1331::DrawArea;
+///
+/// let area = DrawArea::new((10, 10), (19, 29)).unwrap();
+///
+/// assert_eq!(area.top_left(), (10, 10));
+/// assert_eq!(area.bottom_right(), (19, 29));
+/// assert_eq!(area.size(), (10, 20));
+///
+/// // Swapped corners and out of range coordinates are rejected
+/// assert!(DrawArea::new((19, 29), (10, 10)).is_err());
+/// assert!(DrawArea::new((0, 0), (96, 63)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DrawArea {
+    top_left: (u8, u8),
+    bottom_right: (u8, u8),
+}
+
+impl DrawArea {
+    /// Create a new draw area from its inclusive top left and bottom right corners
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if
+    /// the corners are swapped or outside the display's address space.
+    pub fn new(top_left: (u8, u8), bottom_right: (u8, u8)) -> Result<Self, Error> {
+        if bottom_right.0 >= DISPLAY_WIDTH
+            || bottom_right.1 >= DISPLAY_HEIGHT
+            || top_left.0 > bottom_right.0
+            || top_left.1 > bottom_right.1
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(Self {
+            top_left,
+            bottom_right,
+        })
+    }
+
+    /// The whole display
+    pub(crate) fn full() -> Self {
+        Self {
+            top_left: (0, 0),
+            bottom_right: (DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1),
+        }
+    }
+
+    /// Inclusive top left corner
+    pub fn top_left(&self) -> (u8, u8) {
+        self.top_left
+    }
+
+    /// Inclusive bottom right corner
+    pub fn bottom_right(&self) -> (u8, u8) {
+        self.bottom_right
+    }
+
+    /// Width and height in pixels
+    pub fn size(&self) -> (u8, u8) {
+        (
+            self.bottom_right.0 - self.top_left.0 + 1,
+            self.bottom_right.1 - self.top_left.1 + 1,
+        )
+    }
+}
@@ -1,11 +1,113 @@
+use core::fmt;
+
 /// Enum of errors in this crate
 ///
 /// Both error types `CommE` and `PinE` default to `()`
+///
+/// Errors can be formatted with `{}` for logging, which gives a short description followed by the
+/// inner SPI or pin error, if any. embedded-hal doesn't require HAL error types to implement
+/// `Display`, so the inner errors are formatted with their `Debug` implementation.
+///
+/// With the `core-error` feature enabled, `Error` also implements [`core::error::Error`], so it
+/// can be passed to code expecting a `dyn Error`. HAL error types aren't required to implement
+/// `core::error::Error` either, so [`source`](core::error::Error::source) always returns `None`;
+/// the inner error is included in the `Display` output instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use ssd1331::Error;
+///
+/// let error: Error<&str, ()> = Error::Comm("bus fault");
+///
+/// assert_eq!(error.to_string(), "SPI communication error: \"bus fault\"");
+/// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error<CommE = (), PinE = ()> {
     /// Communication error
     Comm(CommE),
 
-    /// Pin setting error
+    /// Pin setting error, from either the DC pin or the RST pin passed to `reset`
     Pin(PinE),
+
+    /// An argument was out of range or otherwise invalid
+    InvalidArgument,
+}
+
+impl<CommE, PinE> fmt::Display for Error<CommE, PinE>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Comm(error) => write!(f, "{}: {:?}", self.kind(), error),
+            Error::Pin(error) => write!(f, "{}: {:?}", self.kind(), error),
+            Error::InvalidArgument => write!(f, "{}", self.kind()),
+        }
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl<CommE, PinE> core::error::Error for Error<CommE, PinE>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+}
+
+impl<CommE, PinE> Error<CommE, PinE> {
+    /// Get the kind of error, without the inner error values
+    ///
+    /// This allows errors to be routed generically, for example into an application error enum,
+    /// without naming the SPI and pin error types. The inner errors are still available by
+    /// matching on `Error` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ssd1331::{Error, ErrorKind};
+    ///
+    /// let error: Error<u8, u8> = Error::Comm(42);
+    ///
+    /// assert_eq!(error.kind(), ErrorKind::Comm);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Comm(_) => ErrorKind::Comm,
+            Error::Pin(_) => ErrorKind::Pin,
+            Error::InvalidArgument => ErrorKind::InvalidArgument,
+        }
+    }
+}
+
+/// The kind of an [`Error`](./enum.Error.html), without any inner error values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorKind {
+    /// Communication error
+    Comm,
+
+    /// Pin setting error, from either the DC pin or the RST pin passed to `reset`
+    Pin,
+
+    /// An argument was out of range or otherwise invalid
+    InvalidArgument,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::Comm => "SPI communication error",
+            ErrorKind::Pin => "pin error",
+            ErrorKind::InvalidArgument => "invalid argument",
+        })
+    }
+}
+
+impl<CommE, PinE> From<Error<CommE, PinE>> for ErrorKind {
+    fn from(error: Error<CommE, PinE>) -> Self {
+        error.kind()
+    }
 }
@@ -0,0 +1,41 @@
+/// Storage for the display's framebuffer
+///
+/// This allows [`Ssd1331`] to either own its framebuffer, or borrow one placed somewhere else by
+/// the application, for example in a static or a particular linker section. It is implemented
+/// for byte arrays, used by [`Ssd1331Owned`] and [`Ssd1331Owned256`], and mutable byte slices,
+/// used by [`Ssd1331Borrowed`].
+///
+/// The storage must be at least 12,288 bytes (96 x 64 pixels, 16 bits per pixel) long, or 6,144
+/// bytes (8 bits per pixel) in 256 colour mode.
+///
+/// [`Ssd1331`]: ./struct.Ssd1331.html
+/// [`Ssd1331Owned`]: ./type.Ssd1331Owned.html
+/// [`Ssd1331Owned256`]: ./type.Ssd1331Owned256.html
+/// [`Ssd1331Borrowed`]: ./type.Ssd1331Borrowed.html
+pub trait FrameBuffer {
+    /// Get the framebuffer bytes
+    fn bytes(&self) -> &[u8];
+
+    /// Get the framebuffer bytes mutably
+    fn bytes_mut(&mut self) -> &mut [u8];
+}
+
+impl<const N: usize> FrameBuffer for [u8; N] {
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl FrameBuffer for &mut [u8] {
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
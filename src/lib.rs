@@ -7,7 +7,8 @@
 //! [`embedded-graphics`] is also supported behind the `graphics` feature flag (on by default).
 //!
 //! Note that the driver requires at least 12288 bytes (96 x 64 pixels, 16 bits per pixel) of memory
-//! to store the display's framebuffer.
+//! to store the display's framebuffer, or 6144 bytes (8 bits per pixel) in 256 colour mode with
+//! [`Ssd1331::new_256`].
 //!
 //! # Examples
 //!
@@ -76,6 +77,32 @@
 //! display.flush().unwrap();
 //! ```
 //!
+//! # Rotation and coordinate spaces
+//!
+//! The display can be rotated in two ways:
+//!
+//! * [`Ssd1331::set_rotation`] (or the rotation passed to [`Ssd1331::new`]) uses the
+//!   controller's address remapping. The framebuffer is laid out in the rotated order and sent
+//!   as-is.
+//! * [`Ssd1331::set_software_rotation`] leaves the controller's remap at its unrotated default
+//!   and rotates coordinates in the driver instead. The framebuffer uses the unrotated 96x64
+//!   layout.
+//!
+//! Both give the same result on the panel. Whichever is used, methods fall into one of two
+//! coordinate spaces:
+//!
+//! * **Logical coordinates** have their origin at the top left of the display as rotated, and are
+//!   bounded by [`Ssd1331::dimensions`]. Framebuffer drawing ([`Ssd1331::set_pixel`],
+//!   `embedded-graphics`) and the accelerated drawing methods like
+//!   [`Ssd1331::draw_rect_border_accel`] use these. Accelerated commands are converted to the
+//!   controller's address space before sending, so they line up with framebuffer contents.
+//! * **Controller addresses** are the controller's 96 columns by 64 rows. Methods that set the
+//!   draw area or address pointer, like [`Ssd1331::set_draw_area`] and [`Ssd1331::set_cursor`],
+//!   use these directly. With hardware rotation the controller maps addresses to the rotated panel
+//!   itself, so where they appear depends on the rotation. With software rotation addresses always
+//!   match the unrotated panel, which makes it the simpler choice when mixing raw address based
+//!   writes with rotated drawing.
+//!
 //! # Features
 //!
 //! ## `graphics` (enabled by default)
@@ -84,10 +111,56 @@
 //! [`embedded-graphics`] crate. This adds the `.draw()` method to the [`Ssd1331`] struct which
 //! accepts any `embedded-graphics` compatible item.
 //!
+//! ## `minifont`
+//!
+//! Enable the `minifont` feature to get access to [`Terminal`], a small text console implementing
+//! [`core::fmt::Write`] with a built in 5x7 pixel font. This is useful for quick on-device debugging
+//! without setting up text rendering with `embedded-graphics`. [`Marquee`] uses the same font to
+//! scroll a text banner of any length across the display.
+//!
+//! ## `le-buffer`
+//!
+//! Store pixels in the framebuffer as little endian RGB565 instead of big endian. This allows
+//! little endian image data (like that used by `ImageRawLE`) to be copied directly into the buffer
+//! returned by [`Ssd1331::buffer_mut`] without converting each pixel. The controller only accepts
+//! big endian pixel data, so bytes are swapped in small chunks as the framebuffer is sent.
+//!
+//! This sets the default [`ByteOrder`]. It can also be chosen at runtime with
+//! [`Ssd1331::init_with`].
+//!
+//! ## `core-error`
+//!
+//! Implement [`core::error::Error`] for [`Error`]. This needs Rust 1.81 or later, newer than the
+//! rest of the crate requires, so it is opt in.
+//!
+//! ## `defmt`
+//!
+//! Implement [`defmt::Format`] for the public types, including [`Error`], [`DisplayRotation`]
+//! and [`Config`], so they can be logged with [`defmt`].
+//!
 //! [embedded-hal]: https://docs.rs/embedded-hal
 //! [`blocking::spi::Write`]: https://docs.rs/embedded-hal/0.2.3/embedded_hal/blocking/spi/trait.Write.html
 //! [`Ssd1331`]: ./struct.Ssd1331.html
+//! [`Ssd1331::new`]: ./struct.Ssd1331.html#method.new
+//! [`Ssd1331::new_256`]: ./struct.Ssd1331.html#method.new_256
+//! [`Ssd1331::set_rotation`]: ./struct.Ssd1331.html#method.set_rotation
+//! [`Ssd1331::set_software_rotation`]: ./struct.Ssd1331.html#method.set_software_rotation
+//! [`Ssd1331::dimensions`]: ./struct.Ssd1331.html#method.dimensions
+//! [`Ssd1331::set_pixel`]: ./struct.Ssd1331.html#method.set_pixel
+//! [`Ssd1331::draw_rect_border_accel`]: ./struct.Ssd1331.html#method.draw_rect_border_accel
+//! [`Ssd1331::set_draw_area`]: ./struct.Ssd1331.html#method.set_draw_area
+//! [`Ssd1331::set_cursor`]: ./struct.Ssd1331.html#method.set_cursor
+//! [`Ssd1331::buffer_mut`]: ./struct.Ssd1331.html#method.buffer_mut
+//! [`Ssd1331::init_with`]: ./struct.Ssd1331.html#method.init_with
+//! [`ByteOrder`]: ./enum.ByteOrder.html
+//! [`Terminal`]: ./struct.Terminal.html
+//! [`Marquee`]: ./struct.Marquee.html
 //! [`embedded-graphics`]: https://docs.rs/embedded-graphics
+//! [`defmt`]: https://docs.rs/defmt
+//! [`defmt::Format`]: https://docs.rs/defmt/latest/defmt/trait.Format.html
+//! [`Error`]: ./enum.Error.html
+//! [`DisplayRotation`]: ./enum.DisplayRotation.html
+//! [`Config`]: ./struct.Config.html
 
 #![no_std]
 // #![deny(missing_debug_implementations)]
@@ -106,11 +179,84 @@ extern crate embedded_hal as hal;
 const DISPLAY_WIDTH: u8 = 96;
 const DISPLAY_HEIGHT: u8 = 64;
 
+/// SPI mode expected by the SSD1331: clock idle low, data captured on the first (rising) edge
+///
+/// The driver can't change how the HAL's SPI peripheral is configured, and can't read anything
+/// back from the display to notice a mismatch, so a wrong mode usually just leaves the display
+/// blank. Use this constant when configuring the peripheral, or compare an existing configuration
+/// against it.
+///
+/// # Examples
+///
+/// ```rust
+/// use embedded_hal::spi::{Mode, Phase, Polarity, MODE_0};
+///
+/// let configured = Mode {
+///     polarity: Polarity::IdleLow,
+///     phase: Phase::CaptureOnFirstTransition,
+/// };
+///
+/// assert!(configured == ssd1331::SPI_MODE);
+/// assert!(ssd1331::SPI_MODE == MODE_0);
+/// ```
+pub const SPI_MODE: hal::spi::Mode = hal::spi::MODE_0;
+
+/// Width of the framebuffer in pixels, the controller's number of columns
+///
+/// The framebuffer always holds the panel's 96x64 pixels, whatever the rotation, but its layout
+/// depends on how it is rotated. With no rotation, 180 degree rotation, or any
+/// [software rotation](./struct.Ssd1331.html#method.set_software_rotation), it is stored as rows
+/// of `FRAME_WIDTH` pixels, [`STRIDE_BYTES`] apart. With 90 or 270 degree hardware rotation it is
+/// stored in the rotated order, as rows of [`FRAME_HEIGHT`] pixels. Use
+/// [`Ssd1331::pixel_index`](./struct.Ssd1331.html#method.pixel_index) to find pixels whatever the
+/// layout.
+pub const FRAME_WIDTH: u8 = DISPLAY_WIDTH;
+
+/// Height of the framebuffer in pixels, the controller's number of rows
+///
+/// See [`FRAME_WIDTH`] for how the framebuffer is laid out.
+pub const FRAME_HEIGHT: u8 = DISPLAY_HEIGHT;
+
+/// Bytes used by each pixel in the framebuffer, in the default 65k colour mode
+///
+/// Pixels are RGB565, in the [`ByteOrder`] chosen for the display. In 256 colour mode each pixel
+/// is a single byte instead; see [`Ssd1331::init_with`](./struct.Ssd1331.html#method.init_with).
+pub const BYTES_PER_PIXEL: usize = 2;
+
+/// Bytes between the start of each unrotated framebuffer row, in the default 65k colour mode
+///
+/// See [`FRAME_WIDTH`] for the rotations this applies to.
+pub const STRIDE_BYTES: usize = FRAME_WIDTH as usize * BYTES_PER_PIXEL;
+
+mod boundspolicy;
+mod byteorder;
 mod command;
+mod config;
 mod display;
 mod displayrotation;
+mod drawarea;
 mod error;
+mod framebuffer;
+#[cfg(feature = "minifont")]
+mod marquee;
+mod op;
+#[cfg(feature = "minifont")]
+mod terminal;
 #[doc(hidden)]
 pub mod test_helpers;
 
-pub use crate::{display::Ssd1331, displayrotation::DisplayRotation, error::Error};
+pub use crate::{
+    boundspolicy::BoundsPolicy,
+    byteorder::ByteOrder,
+    command::{ColorMode, NFrames, VcomhLevel},
+    config::Config,
+    display::{Ssd1331, Ssd1331Borrowed, Ssd1331Owned, Ssd1331Owned256},
+    displayrotation::DisplayRotation,
+    drawarea::DrawArea,
+    error::{Error, ErrorKind},
+    framebuffer::FrameBuffer,
+    op::Op,
+};
+
+#[cfg(feature = "minifont")]
+pub use crate::{marquee::Marquee, terminal::Terminal};
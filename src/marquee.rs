@@ -0,0 +1,189 @@
+//! Scrolling text banner
+
+use hal::{blocking::delay::DelayMs, digital::v2::OutputPin};
+
+use crate::{
+    display::{Ssd1331, BUF_SIZE},
+    error::Error,
+    framebuffer::FrameBuffer,
+    terminal::{cell_column, CELL_HEIGHT, CELL_WIDTH},
+};
+
+/// Time `tick` waits before each step
+const STEP_MS: u8 = 20;
+
+/// Text banner that scrolls right to left across a band of the display
+///
+/// The text can be any length, as only the part currently visible is drawn. Each call to
+/// [`tick`](#method.tick) moves it one pixel left, redraws the band into the framebuffer and
+/// sends just that band, so the rest of the display is left alone. Text enters from the right hand
+/// edge, and once it has scrolled completely off the left it starts again from the right.
+///
+/// The band is 8 pixels tall, starting at logical row `top`, and uses the same built in 5x7 pixel
+/// font as [`Terminal`](./struct.Terminal.html). The font is fixed width, so each character is 6
+/// pixels wide including spacing. Bytes outside printable ASCII are shown as `?`.
+///
+/// This requires the `minifont` feature to be enabled.
+///
+/// # Tick cadence
+///
+/// `tick` waits 20ms with the given delay before each step, so calling it in a loop scrolls at
+/// about 50 pixels per second, less the time taken to send the band. Each step sends one band of
+/// 8 full width rows: 1,536 bytes with no rotation.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ssd1331::test_helpers::{Delay, Pin, Spi};
+/// use ssd1331::{DisplayRotation::Rotate0, Marquee, Ssd1331};
+///
+/// // Set up SPI interface, digital pin and delay. These are stub implementations used in
+/// // examples.
+/// let spi = Spi;
+/// let dc = Pin;
+/// let mut delay = Delay;
+///
+/// let mut display = Ssd1331::new(spi, dc, Rotate0);
+/// display.init().unwrap();
+///
+/// let mut marquee = Marquee::new(&mut display, "Next train: 12:04 to Central", 56);
+///
+/// for _ in 0..100 {
+///     marquee.tick(&mut delay).unwrap();
+/// }
+/// ```
+///
+/// # Why not hardware scrolling?
+///
+/// The SSD1331 can scroll a band of rows continuously by itself, but it moves on its own clock and
+/// can't be read back over SPI. There's no way to know which columns have just come into view, so
+/// text longer than the display can't be drawn into them reliably. `Marquee` redraws the band on
+/// each step instead, which keeps it in step with the text at the cost of sending the band.
+pub struct Marquee<'a, SPI, DC, B = [u8; BUF_SIZE]> {
+    display: &'a mut Ssd1331<SPI, DC, B>,
+
+    /// Text to scroll
+    text: &'a str,
+
+    /// Logical row at the top of the band
+    top: u8,
+
+    /// Scroll position in pixels, from 0 to the text width plus the display width
+    offset: u32,
+
+    /// Text colour
+    foreground: u16,
+
+    /// Background colour
+    background: u16,
+}
+
+impl<'a, SPI, DC, B, CommE, PinE> Marquee<'a, SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+    B: FrameBuffer,
+{
+    /// Create a new marquee drawing white text on a black background
+    ///
+    /// Nothing is drawn until the first [`tick`](#method.tick).
+    pub fn new(display: &'a mut Ssd1331<SPI, DC, B>, text: &'a str, top: u8) -> Self {
+        Self::with_colors(display, text, top, 0xffff, 0x0000)
+    }
+
+    /// Create a new marquee with the given RGB565 text and background colours
+    ///
+    /// Nothing is drawn until the first [`tick`](#method.tick).
+    pub fn with_colors(
+        display: &'a mut Ssd1331<SPI, DC, B>,
+        text: &'a str,
+        top: u8,
+        foreground: u16,
+        background: u16,
+    ) -> Self {
+        Self {
+            display,
+            text,
+            top,
+            offset: 0,
+            foreground,
+            background,
+        }
+    }
+
+    /// Wait for the next step, then scroll the text one pixel left and send the band
+    ///
+    /// An [`InvalidArgument`](./enum.Error.html#variant.InvalidArgument) error is returned if the
+    /// band extends past the bottom of the display.
+    pub fn tick<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), Error<CommE, PinE>>
+    where
+        DELAY: DelayMs<u8>,
+    {
+        let (w, h) = self.display.dimensions();
+        let w = u32::from(w);
+
+        if u32::from(self.top) + CELL_HEIGHT > u32::from(h) {
+            return Err(Error::InvalidArgument);
+        }
+
+        delay.delay_ms(STEP_MS);
+
+        let text = self.text.as_bytes();
+        let text_width = text.len() as u32 * CELL_WIDTH;
+
+        // The text scrolls in from the right edge and fully off the left before repeating
+        self.offset = (self.offset + 1) % (text_width + w);
+
+        for x in 0..w {
+            let bits = match (self.offset + x).checked_sub(w) {
+                Some(tx) if tx < text_width => {
+                    cell_column(text[(tx / CELL_WIDTH) as usize], tx % CELL_WIDTH)
+                }
+                _ => 0,
+            };
+
+            for y in 0..CELL_HEIGHT {
+                let color = if (bits >> y) & 1 == 1 {
+                    self.foreground
+                } else {
+                    self.background
+                };
+
+                self.display.set_pixel(x, u32::from(self.top) + y, color);
+            }
+        }
+
+        self.display.flush_rows(self.top, CELL_HEIGHT as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_helpers::{Delay, Pin, Spi},
+        DisplayRotation,
+    };
+
+    #[test]
+    fn text_enters_from_right() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+
+        let mut marquee = Marquee::new(&mut display, "I", 8);
+
+        // The first column of 'I' is empty, the second is 0x41
+        marquee.tick(&mut Delay).unwrap();
+        marquee.tick(&mut Delay).unwrap();
+
+        let lit = |display: &mut Ssd1331<Spi, Pin>, x: usize, y: usize| {
+            let idx = (y * 96 + x) * 2;
+
+            display.buffer_mut()[idx..idx + 2] == [0xff, 0xff]
+        };
+
+        assert!(lit(&mut display, 95, 8));
+        assert!(!lit(&mut display, 95, 9));
+        assert!(lit(&mut display, 95, 14));
+        assert!(!lit(&mut display, 94, 8));
+    }
+}
@@ -0,0 +1,33 @@
+/// One step of a recorded transfer to the display
+///
+/// Returned by [`record_flush`], which describes a flush without sending it so it can be
+/// submitted through a DMA or other transaction mechanism. Each op is a single SPI write, with the
+/// data/command pin set low for [`Command`](#variant.Command) and high for [`Data`](#variant.Data)
+/// before the bytes are sent.
+///
+/// Ops borrow the display's framebuffer, so it can't be drawn to until they are dropped. Data must
+/// be sent before the framebuffer is changed again, otherwise part of the next frame may be sent.
+///
+/// [`record_flush`]: ./struct.Ssd1331.html#method.record_flush
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Op<'a> {
+    /// Command bytes, sent with the data/command pin low
+    Command(&'a [u8]),
+    /// Pixel data, sent with the data/command pin high
+    Data(&'a [u8]),
+}
+
+impl<'a> Op<'a> {
+    /// Whether this op is sent with the data/command pin low
+    pub fn is_command(&self) -> bool {
+        matches!(self, Op::Command(_))
+    }
+
+    /// The bytes to send
+    pub fn bytes(&self) -> &'a [u8] {
+        match self {
+            Op::Command(bytes) | Op::Data(bytes) => bytes,
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! Minimal text terminal for quick on-device debugging
+
+use core::fmt;
+
+use hal::digital::v2::OutputPin;
+
+use crate::{
+    display::{Ssd1331, BUF_SIZE},
+    framebuffer::FrameBuffer,
+};
+
+/// Width of a glyph in pixels
+const GLYPH_WIDTH: u32 = 5;
+
+/// Height of a glyph in pixels
+const GLYPH_HEIGHT: u32 = 7;
+
+/// Width of a character cell, including one column of spacing
+pub(crate) const CELL_WIDTH: u32 = GLYPH_WIDTH + 1;
+
+/// Height of a character cell, including one row of spacing
+pub(crate) const CELL_HEIGHT: u32 = GLYPH_HEIGHT + 1;
+
+/// Most columns that fit across the display in any rotation (96 / 6)
+const MAX_COLUMNS: usize = 16;
+
+/// Most lines that fit down the display in any rotation (96 / 8)
+const MAX_LINES: usize = 12;
+
+/// Text terminal that renders into the display's framebuffer
+///
+/// `Terminal` implements [`core::fmt::Write`], so `write!()` and `writeln!()` can be used to print
+/// debug output without pulling in a full text layout library. Text is rendered with a built in
+/// 5x7 pixel ASCII font in 6x8 pixel cells, giving 16 columns by 8 lines with `Rotate0` or
+/// `Rotate180`, and 10 columns by 12 lines with `Rotate90` or `Rotate270`.
+///
+/// Lines wrap when they reach the right edge of the display and the terminal scrolls up by one line
+/// when the bottom is reached. `\n` starts a new line and `\r` returns to the start of the current
+/// line. Characters outside printable ASCII are shown as `?`.
+///
+/// Only the framebuffer is updated; call [`flush`](#method.flush) to send it to the display.
+///
+/// This requires the `minifont` feature to be enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ssd1331::test_helpers::{Pin, Spi};
+/// use core::fmt::Write;
+/// use ssd1331::{DisplayRotation::Rotate0, Ssd1331, Terminal};
+///
+/// // Set up SPI interface and digital pin. These are stub implementations used in examples.
+/// let spi = Spi;
+/// let dc = Pin;
+///
+/// let mut display = Ssd1331::new(spi, dc, Rotate0);
+/// display.init().unwrap();
+///
+/// let mut term = Terminal::new(&mut display);
+///
+/// let x = 42;
+/// writeln!(term, "x={}", x).unwrap();
+///
+/// term.flush().unwrap();
+/// ```
+pub struct Terminal<'a, SPI, DC, B = [u8; BUF_SIZE]> {
+    display: &'a mut Ssd1331<SPI, DC, B>,
+
+    /// Characters currently on screen, used to redraw when scrolling
+    cells: [[u8; MAX_COLUMNS]; MAX_LINES],
+
+    /// Cursor column
+    column: usize,
+
+    /// Cursor line
+    line: usize,
+
+    /// Text colour
+    foreground: u16,
+
+    /// Background colour
+    background: u16,
+}
+
+impl<'a, SPI, DC, B, CommE, PinE> Terminal<'a, SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+    B: FrameBuffer,
+{
+    /// Create a new terminal drawing white text on a black background
+    ///
+    /// The framebuffer is cleared and the cursor placed in the top left corner.
+    pub fn new(display: &'a mut Ssd1331<SPI, DC, B>) -> Self {
+        Self::with_colors(display, 0xffff, 0x0000)
+    }
+
+    /// Create a new terminal with the given RGB565 text and background colours
+    ///
+    /// The framebuffer is cleared to the background colour and the cursor placed in the top left
+    /// corner.
+    pub fn with_colors(
+        display: &'a mut Ssd1331<SPI, DC, B>,
+        foreground: u16,
+        background: u16,
+    ) -> Self {
+        let mut term = Self {
+            display,
+            cells: [[b' '; MAX_COLUMNS]; MAX_LINES],
+            column: 0,
+            line: 0,
+            foreground,
+            background,
+        };
+
+        term.clear();
+
+        term
+    }
+
+    /// Clear the terminal and move the cursor to the top left corner
+    pub fn clear(&mut self) {
+        self.cells = [[b' '; MAX_COLUMNS]; MAX_LINES];
+        self.column = 0;
+        self.line = 0;
+
+        let (w, h) = self.display.dimensions();
+
+        for y in 0..u32::from(h) {
+            for x in 0..u32::from(w) {
+                self.display.set_pixel(x, y, self.background);
+            }
+        }
+    }
+
+    /// Send the framebuffer to the display
+    pub fn flush(&mut self) -> Result<(), crate::Error<CommE, PinE>> {
+        self.display.flush()
+    }
+
+    /// Number of columns and lines that fit on the display in its current rotation
+    fn size(&self) -> (usize, usize) {
+        let (w, h) = self.display.dimensions();
+
+        (
+            (u32::from(w) / CELL_WIDTH) as usize,
+            (u32::from(h) / CELL_HEIGHT) as usize,
+        )
+    }
+
+    fn new_line(&mut self) {
+        let (_, lines) = self.size();
+
+        self.column = 0;
+        self.line += 1;
+
+        if self.line >= lines {
+            self.cells.copy_within(1..lines, 0);
+            self.cells[lines - 1] = [b' '; MAX_COLUMNS];
+            self.line = lines - 1;
+
+            self.redraw();
+        }
+    }
+
+    fn redraw(&mut self) {
+        let (columns, lines) = self.size();
+
+        for line in 0..lines {
+            for column in 0..columns {
+                self.draw_cell(column, line);
+            }
+        }
+    }
+
+    fn draw_cell(&mut self, column: usize, line: usize) {
+        let c = self.cells[line][column];
+
+        let x0 = column as u32 * CELL_WIDTH;
+        let y0 = line as u32 * CELL_HEIGHT;
+
+        for x in 0..CELL_WIDTH {
+            let bits = cell_column(c, x);
+
+            for y in 0..CELL_HEIGHT {
+                let color = if (bits >> y) & 1 == 1 {
+                    self.foreground
+                } else {
+                    self.background
+                };
+
+                self.display.set_pixel(x0 + x, y0 + y, color);
+            }
+        }
+    }
+}
+
+impl<'a, SPI, DC, B, CommE, PinE> fmt::Write for Terminal<'a, SPI, DC, B>
+where
+    SPI: hal::blocking::spi::Write<u8, Error = CommE>,
+    DC: OutputPin<Error = PinE>,
+    B: FrameBuffer,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '\n' => self.new_line(),
+                '\r' => self.column = 0,
+                c => {
+                    let (columns, _) = self.size();
+
+                    if self.column >= columns {
+                        self.new_line();
+                    }
+
+                    let c = if (' '..='~').contains(&c) {
+                        c as u8
+                    } else {
+                        b'?'
+                    };
+
+                    self.cells[self.line][self.column] = c;
+                    self.draw_cell(self.column, self.line);
+                    self.column += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Pixels in column `x` of the character cell for `c`, with the top row in the least significant
+/// bit
+///
+/// Characters outside printable ASCII are drawn as `?`. The spacing column past the glyph is
+/// empty.
+pub(crate) fn cell_column(c: u8, x: u32) -> u8 {
+    let c = if (b' '..=b'~').contains(&c) { c } else { b'?' };
+
+    FONT[usize::from(c - b' ')]
+        .get(x as usize)
+        .copied()
+        .unwrap_or(0)
+}
+
+/// 5x7 pixel font covering printable ASCII (`' '` to `'~'`)
+///
+/// Each glyph is five columns, left to right, with the top row in the least significant bit.
+const FONT: [[u8; GLYPH_WIDTH as usize]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5f, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7f, 0x14, 0x7f, 0x14], // '#'
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1c, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1c, 0x00], // ')'
+    [0x14, 0x08, 0x3e, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3e, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], // '0'
+    [0x00, 0x42, 0x7f, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4b, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7f, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3c, 0x4a, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1e], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3e], // '@'
+    [0x7e, 0x11, 0x11, 0x11, 0x7e], // 'A'
+    [0x7f, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3e, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], // 'D'
+    [0x7f, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7f, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3e, 0x41, 0x49, 0x49, 0x7a], // 'G'
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], // 'H'
+    [0x00, 0x41, 0x7f, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3f, 0x01], // 'J'
+    [0x7f, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7f, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7f, 0x02, 0x0c, 0x02, 0x7f], // 'M'
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], // 'N'
+    [0x3e, 0x41, 0x41, 0x41, 0x3e], // 'O'
+    [0x7f, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3e, 0x41, 0x51, 0x21, 0x5e], // 'Q'
+    [0x7f, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7f, 0x01, 0x01], // 'T'
+    [0x3f, 0x40, 0x40, 0x40, 0x3f], // 'U'
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], // 'V'
+    [0x3f, 0x40, 0x38, 0x40, 0x3f], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7f, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7f, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7f, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7f], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7e, 0x09, 0x01, 0x02], // 'f'
+    [0x0c, 0x52, 0x52, 0x52, 0x3e], // 'g'
+    [0x7f, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7d, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3d, 0x00], // 'j'
+    [0x7f, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7f, 0x40, 0x00], // 'l'
+    [0x7c, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7c, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7c, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7c], // 'q'
+    [0x7c, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3f, 0x44, 0x40, 0x20], // 't'
+    [0x3c, 0x40, 0x40, 0x20, 0x7c], // 'u'
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], // 'v'
+    [0x3c, 0x40, 0x30, 0x40, 0x3c], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0c, 0x50, 0x50, 0x50, 0x3c], // 'y'
+    [0x44, 0x64, 0x54, 0x4c, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7f, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_helpers::{Pin, Spi},
+        DisplayRotation,
+    };
+    use core::fmt::Write;
+
+    #[test]
+    fn newline_and_carriage_return() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+        let mut term = Terminal::new(&mut display);
+
+        write!(term, "ab\rc\nd").unwrap();
+
+        assert_eq!(&term.cells[0][..3], b"cb ");
+        assert_eq!(&term.cells[1][..2], b"d ");
+        assert_eq!((term.line, term.column), (1, 1));
+    }
+
+    #[test]
+    fn wraps_at_right_edge() {
+        for &(rotation, columns) in &[
+            (DisplayRotation::Rotate0, 16),
+            (DisplayRotation::Rotate90, 10),
+        ] {
+            let mut display = Ssd1331::new(Spi, Pin, rotation);
+            let mut term = Terminal::new(&mut display);
+
+            for _ in 0..columns {
+                write!(term, "x").unwrap();
+            }
+
+            // The cursor sits past the last column until the next character is written
+            assert_eq!((term.line, term.column), (0, columns));
+
+            write!(term, "y").unwrap();
+
+            assert!(term.cells[0][..columns].iter().all(|&c| c == b'x'));
+            assert_eq!(term.cells[1][0], b'y');
+            assert_eq!((term.line, term.column), (1, 1));
+        }
+    }
+
+    #[test]
+    fn scrolls_when_full() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+        let mut term = Terminal::with_colors(&mut display, 0xffff, 0x001f);
+
+        write!(term, "0\n1\n2\n3\n4\n5\n6\n7").unwrap();
+
+        let mut line_1 = [0u16; 96 * 8];
+
+        for y in 0..8 {
+            for x in 0..96 {
+                line_1[y * 96 + x] = term.display.get_pixel(x as u32, y as u32 + 8).unwrap();
+            }
+        }
+
+        // Starting a 9th line scrolls everything up by one line
+        writeln!(term).unwrap();
+
+        assert_eq!(term.cells[0][0], b'1');
+        assert_eq!(term.cells[6][0], b'7');
+        assert!(term.cells[7].iter().all(|&c| c == b' '));
+        assert_eq!((term.line, term.column), (7, 0));
+
+        for y in 0..8 {
+            for x in 0..96 {
+                assert_eq!(
+                    term.display.get_pixel(x as u32, y as u32),
+                    Some(line_1[y * 96 + x])
+                );
+                assert_eq!(
+                    term.display.get_pixel(x as u32, y as u32 + 56),
+                    Some(0x001f)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn draws_glyph() {
+        let mut display = Ssd1331::new(Spi, Pin, DisplayRotation::Rotate0);
+        let mut term = Terminal::with_colors(&mut display, 0xf800, 0x001f);
+
+        // 'A' is [0x7e, 0x11, 0x11, 0x11, 0x7e], drawn in the second cell
+        write!(term, " A").unwrap();
+
+        let pixel = |term: &Terminal<Spi, Pin>, x: u32, y: u32| term.display.get_pixel(6 + x, y);
+
+        assert_eq!(pixel(&term, 0, 0), Some(0x001f));
+        assert_eq!(pixel(&term, 0, 1), Some(0xf800));
+        assert_eq!(pixel(&term, 0, 6), Some(0xf800));
+        assert_eq!(pixel(&term, 0, 7), Some(0x001f));
+        assert_eq!(pixel(&term, 1, 0), Some(0xf800));
+        assert_eq!(pixel(&term, 1, 1), Some(0x001f));
+        assert_eq!(pixel(&term, 1, 4), Some(0xf800));
+        assert_eq!(pixel(&term, 4, 1), Some(0xf800));
+
+        // Spacing column between glyphs
+        for y in 0..8 {
+            assert_eq!(pixel(&term, 5, y), Some(0x001f));
+        }
+    }
+}
@@ -1,10 +1,17 @@
 //! Helpers for use in examples and tests
 
+use core::cell::Cell;
+
 use embedded_hal::{
-    blocking::spi::{self, Transfer},
+    blocking::{
+        delay::DelayMs,
+        spi::{self, Transfer},
+    },
     digital::v2::OutputPin,
 };
 
+use crate::{DisplayRotation, Ssd1331};
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy)]
 pub struct Spi;
@@ -40,3 +47,299 @@ impl OutputPin for Pin {
         Ok(())
     }
 }
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct Delay;
+
+impl DelayMs<u8> for Delay {
+    fn delay_ms(&mut self, _ms: u8) {}
+}
+
+/// SPI stub that records every written byte into a caller-provided buffer
+///
+/// Writes fail once the buffer is full.
+#[derive(Debug)]
+pub struct CaptureSpi<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> CaptureSpi<'a> {
+    /// Create a new capture into `buf`
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Bytes written so far
+    pub fn written(&self) -> &[u8] {
+        &self.buf[0..self.len]
+    }
+}
+
+impl<'a> spi::Write<u8> for CaptureSpi<'a> {
+    type Error = ();
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), ()> {
+        let end = self.len + buf.len();
+
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(())?
+            .copy_from_slice(buf);
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+/// SPI stub that counts writes and bytes written, for measuring how much a method sends
+///
+/// # Examples
+///
+/// ```rust
+/// use ssd1331::{
+///     test_helpers::{CountingSpi, Pin},
+///     DisplayRotation::Rotate0,
+///     Ssd1331,
+/// };
+///
+/// let mut display = Ssd1331::new(CountingSpi::default(), Pin, Rotate0);
+///
+/// display.flush_solid(0x0000).unwrap();
+///
+/// let (spi, _dc) = display.release();
+///
+/// // Draw area commands followed by 96 * 64 pixels
+/// assert_eq!(spi.bytes, 6 + 96 * 64 * 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingSpi {
+    /// Number of calls to `write`
+    pub writes: usize,
+
+    /// Total number of bytes written
+    pub bytes: usize,
+}
+
+impl spi::Write<u8> for CountingSpi {
+    type Error = ();
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), ()> {
+        self.writes += 1;
+        self.bytes += buf.len();
+
+        Ok(())
+    }
+}
+
+/// DC pin stub that shares its state with a [`RecordingSpi`]
+///
+/// This is only intended for tests.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordingPin<'a> {
+    state: &'a Cell<bool>,
+}
+
+impl<'a> RecordingPin<'a> {
+    /// Create a new pin, storing its state in `state`
+    pub fn new(state: &'a Cell<bool>) -> Self {
+        Self { state }
+    }
+}
+
+impl<'a> OutputPin for RecordingPin<'a> {
+    type Error = ();
+
+    fn set_high(&mut self) -> Result<(), ()> {
+        self.state.set(true);
+
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), ()> {
+        self.state.set(false);
+
+        Ok(())
+    }
+}
+
+/// SPI stub that records every written byte along with the DC pin state when it was written
+///
+/// The DC state is read from the [`RecordingPin`] sharing the same `Cell`: `false` for command
+/// bytes and `true` for data. This allows golden tests of the exact byte stream sent for
+/// initialisation, flushing and command encoding. Writes fail once the log is full.
+///
+/// This is only intended for tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::cell::Cell;
+/// use ssd1331::{
+///     test_helpers::{RecordingPin, RecordingSpi},
+///     DisplayRotation::Rotate0,
+///     Ssd1331,
+/// };
+///
+/// let dc_state = Cell::new(false);
+/// let mut log = [(false, 0u8); 16];
+///
+/// let mut display = Ssd1331::new(
+///     RecordingSpi::new(&mut log, &dc_state),
+///     RecordingPin::new(&dc_state),
+///     Rotate0,
+/// );
+///
+/// display.set_draw_area((0, 0), (0, 0)).unwrap();
+/// display.write_data(&[0xf8, 0x00]).unwrap();
+///
+/// let (spi, _dc) = display.release();
+///
+/// assert!(spi.commands().eq([0x15, 0, 0, 0x75, 0, 0].iter().copied()));
+/// assert!(spi.data().eq([0xf8, 0x00].iter().copied()));
+/// ```
+#[derive(Debug)]
+pub struct RecordingSpi<'a> {
+    log: &'a mut [(bool, u8)],
+    len: usize,
+    dc: &'a Cell<bool>,
+}
+
+impl<'a> RecordingSpi<'a> {
+    /// Create a new recorder logging into `log`, reading the DC state from `dc`
+    pub fn new(log: &'a mut [(bool, u8)], dc: &'a Cell<bool>) -> Self {
+        Self { log, len: 0, dc }
+    }
+
+    /// Every byte written so far, with the DC state when it was written
+    pub fn log(&self) -> &[(bool, u8)] {
+        &self.log[0..self.len]
+    }
+
+    /// Bytes written in command mode (DC low)
+    pub fn commands(&self) -> impl Iterator<Item = u8> + '_ {
+        self.log()
+            .iter()
+            .filter(|(dc, _)| !*dc)
+            .map(|(_, byte)| *byte)
+    }
+
+    /// Bytes written in data mode (DC high)
+    pub fn data(&self) -> impl Iterator<Item = u8> + '_ {
+        self.log()
+            .iter()
+            .filter(|(dc, _)| *dc)
+            .map(|(_, byte)| *byte)
+    }
+}
+
+impl<'a> spi::Write<u8> for RecordingSpi<'a> {
+    type Error = ();
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), ()> {
+        let end = self.len + buf.len();
+        let dc = self.dc.get();
+
+        for (entry, byte) in self
+            .log
+            .get_mut(self.len..end)
+            .ok_or(())?
+            .iter_mut()
+            .zip(buf)
+        {
+            *entry = (dc, *byte);
+        }
+
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+/// Write the bytes `init()` sends for the given rotation into `out`, returning how many were
+/// written
+///
+/// Panics if `out` is too small to hold the whole sequence.
+pub fn init_sequence_bytes(rotation: DisplayRotation, out: &mut [u8]) -> usize {
+    let mut display = Ssd1331::new(CaptureSpi::new(out), Pin, rotation);
+
+    display
+        .init()
+        .expect("init sequence does not fit in buffer");
+
+    let (spi, _dc) = display.release();
+
+    spi.len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The init sequence for a given remap byte
+    fn expected(remap: u8) -> [u8; 25] {
+        [
+            0xAE, // Display off
+            0xB3, 0xF0, // Clock divider and oscillator frequency
+            0xA8, 0x3F, // Multiplex ratio
+            0xA1, 0x00, // Start line
+            0xA2, 0x00, // Display offset
+            0xA0, remap, // Remap and colour depth
+            0x81, 0x91, 0x82, 0x50, 0x83, 0x7D, // Contrast
+            0x87, 0x0F, // Master current
+            0xB1, 0xF1, // Precharge period
+            0xBE, 0x30, // Vcomh deselect level
+            0xA4, // Normal (non-inverted) display
+            0xAF, // Display on
+        ]
+    }
+
+    #[test]
+    fn init_sequence() {
+        let cases = [
+            (DisplayRotation::Rotate0, 0x60),
+            (DisplayRotation::Rotate90, 0x63),
+            (DisplayRotation::Rotate180, 0x72),
+            (DisplayRotation::Rotate270, 0x71),
+        ];
+
+        for (rotation, remap) in cases.iter() {
+            let mut out = [0xffu8; 25 + 6 + 96 * 64 * 2];
+            let len = init_sequence_bytes(*rotation, &mut out);
+            let expected = expected(*remap);
+            let (setup, rest) = out[0..len].split_at(24);
+            let (clear, display_on) = rest.split_at(rest.len() - 1);
+
+            assert_eq!(len, out.len(), "{:?}", rotation);
+            assert_eq!(*setup, expected[0..24], "{:?}", rotation);
+            // Full draw area, then a black frame before the display is turned on
+            assert_eq!(clear[0..6], [0x15, 0, 95, 0x75, 0, 63], "{:?}", rotation);
+            assert!(clear[6..].iter().all(|b| *b == 0), "{:?}", rotation);
+            assert_eq!(*display_on, expected[24..], "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn flush_dc_states() {
+        let dc_state = Cell::new(false);
+        let mut log = [(false, 0u8); 6 + 96 * 64 * 2];
+        let mut display = Ssd1331::new(
+            RecordingSpi::new(&mut log, &dc_state),
+            RecordingPin::new(&dc_state),
+            DisplayRotation::Rotate0,
+        );
+
+        display.flush().unwrap();
+
+        let (spi, _dc) = display.release();
+
+        // Draw area in command mode, then the whole frame as data
+        assert!(spi
+            .commands()
+            .eq([0x15, 0, 95, 0x75, 0, 63].iter().copied()));
+        assert!(spi.log()[0..6].iter().all(|(dc, _)| !*dc));
+        assert!(spi.log()[6..].iter().all(|(dc, _)| *dc));
+        assert_eq!(spi.data().count(), 96 * 64 * 2);
+    }
+}